@@ -0,0 +1,57 @@
+// Adds a "Reroll Seed" button to the menus of the template, without touching `Actions`
+use bevy::prelude::*;
+use bevy_game::menu::{Actions, CustomMenuEvent, GameCfg, RegisterScreen, Screens};
+use bevy_game::rng::GameRng;
+use bevy_game::GamePlugin;
+use bevy_quickmenu::{ActionTrait, MenuItem, MenuState};
+use rand::Rng;
+
+/// Prefixed by the game, to keep it apart from the buttons of other plugins
+const REROLL_SEED: &str = "example:reroll_seed";
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "Custom action".to_string(),
+                resolution: (800., 600.).into(),
+                canvas: Some("#bevy".to_owned()),
+                ..default()
+            }),
+            ..default()
+        }))
+        .add_plugin(GamePlugin::default())
+        .register_screen("Extras", extras)
+        .add_system(reroll_seed)
+        .run();
+}
+
+fn extras(cfg: &GameCfg) -> Vec<MenuItem<Screens>> {
+    vec![
+        MenuItem::headline("Extras"),
+        MenuItem::label(format!("Seed: {}", cfg.seed)),
+        MenuItem::action("Reroll Seed", Actions::CustomAction(REROLL_SEED)),
+        MenuItem::action("Back", Actions::Back),
+    ]
+}
+
+/// Sets a new seed through `Actions::SetSeed` on the menu, so that it shows the seed and saves it.
+/// It is drawn from the `GameRng` of the game in progress, or from a new one without a game
+fn reroll_seed(
+    mut custom_event: EventReader<CustomMenuEvent>,
+    mut action_event: EventWriter<Actions>,
+    menu_state: Option<ResMut<MenuState<Screens>>>,
+    mut rng: Option<ResMut<GameRng>>,
+) {
+    let Some(mut menu_state) = menu_state else { return };
+    for CustomMenuEvent(id) in custom_event.iter() {
+        if *id == REROLL_SEED {
+            // 0 would pick a random seed for every game
+            let seed = match rng.as_mut() {
+                Some(rng) => rng.gen_range(1..=u64::MAX),
+                None => GameRng::new(0).seed(),
+            };
+            Actions::SetSeed(seed).handle(menu_state.state_mut(), &mut action_event);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+// Adds a "Leaderboard" screen to the menus of the template, without touching `Screens`, and
+// titles them after the game
+use bevy::prelude::*;
+use bevy_game::menu::{Actions, GameCfg, MenuPlugin, RegisterScreen, Screens};
+use bevy_game::GamePlugin;
+use bevy_quickmenu::MenuItem;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "Custom screen".to_string(),
+                resolution: (800., 600.).into(),
+                canvas: Some("#bevy".to_owned()),
+                ..default()
+            }),
+            ..default()
+        }))
+        .add_plugin(GamePlugin::with_menu(MenuPlugin::new().default_config(
+            GameCfg {
+                title: "Custom screen",
+                ..default()
+            },
+        )))
+        .register_screen("Leaderboard", leaderboard)
+        .run();
+}
+
+fn leaderboard(cfg: &GameCfg) -> Vec<MenuItem<Screens>> {
+    vec![
+        MenuItem::headline("Leaderboard"),
+        MenuItem::label(format!("Best seed: {}", cfg.game_seed)),
+        MenuItem::label("1. You"),
+        MenuItem::action("Back", Actions::Back),
+    ]
+}
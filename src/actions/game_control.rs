@@ -1,33 +1,116 @@
 use bevy::prelude::{Input, KeyCode, Res};
+use serde::{Deserialize, Serialize};
 
+/// The rebindable inputs, see `KeyBindings`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum GameControl {
     Up,
     Down,
     Left,
     Right,
+    Jump,
+    Interact,
+    Pause,
 }
 
 impl GameControl {
-    pub fn pressed(&self, keyboard_input: &Res<Input<KeyCode>>) -> bool {
+    pub const ALL: [Self; 7] = [
+        Self::Up,
+        Self::Down,
+        Self::Left,
+        Self::Right,
+        Self::Jump,
+        Self::Interact,
+        Self::Pause,
+    ];
+
+    pub fn name(&self) -> &'static str {
         match self {
-            GameControl::Up => {
-                keyboard_input.pressed(KeyCode::W) || keyboard_input.pressed(KeyCode::Up)
-            }
-            GameControl::Down => {
-                keyboard_input.pressed(KeyCode::S) || keyboard_input.pressed(KeyCode::Down)
-            }
-            GameControl::Left => {
-                keyboard_input.pressed(KeyCode::A) || keyboard_input.pressed(KeyCode::Left)
-            }
-            GameControl::Right => {
-                keyboard_input.pressed(KeyCode::D) || keyboard_input.pressed(KeyCode::Right)
-            }
+            GameControl::Up => "Up",
+            GameControl::Down => "Down",
+            GameControl::Left => "Left",
+            GameControl::Right => "Right",
+            GameControl::Jump => "Jump",
+            GameControl::Interact => "Interact",
+            GameControl::Pause => "Pause",
         }
     }
+
+    /// The arrow keys always move, next to the bound keys
+    pub fn pressed(&self, keys: &KeyBindings, keyboard_input: &Res<Input<KeyCode>>) -> bool {
+        let arrow = match self {
+            GameControl::Up => Some(KeyCode::Up),
+            GameControl::Down => Some(KeyCode::Down),
+            GameControl::Left => Some(KeyCode::Left),
+            GameControl::Right => Some(KeyCode::Right),
+            GameControl::Jump | GameControl::Interact | GameControl::Pause => None,
+        };
+        keyboard_input.pressed(keys.key(*self))
+            || arrow.map_or(false, |arrow| keyboard_input.pressed(arrow))
+    }
+}
+
+/// Keys bound to each `GameControl`, rebindable from the `Controls` screen and persisted with the
+/// rest of the config
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub up: KeyCode,
+    pub down: KeyCode,
+    pub left: KeyCode,
+    pub right: KeyCode,
+    pub jump: KeyCode,
+    pub interact: KeyCode,
+    pub pause: KeyCode,
+    /// Dev hotkey starting a new game, see `GameCfg::dev_hotkeys`. Not a `GameControl`, so it is
+    /// only rebindable in the config file
+    pub new_game: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            up: KeyCode::W,
+            down: KeyCode::S,
+            left: KeyCode::A,
+            right: KeyCode::D,
+            jump: KeyCode::Space,
+            interact: KeyCode::E,
+            pause: KeyCode::Escape,
+            new_game: KeyCode::F5,
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn key(&self, control: GameControl) -> KeyCode {
+        match control {
+            GameControl::Up => self.up,
+            GameControl::Down => self.down,
+            GameControl::Left => self.left,
+            GameControl::Right => self.right,
+            GameControl::Jump => self.jump,
+            GameControl::Interact => self.interact,
+            GameControl::Pause => self.pause,
+        }
+    }
+
+    pub fn set(&mut self, control: GameControl, key: KeyCode) {
+        let binding = match control {
+            GameControl::Up => &mut self.up,
+            GameControl::Down => &mut self.down,
+            GameControl::Left => &mut self.left,
+            GameControl::Right => &mut self.right,
+            GameControl::Jump => &mut self.jump,
+            GameControl::Interact => &mut self.interact,
+            GameControl::Pause => &mut self.pause,
+        };
+        *binding = key;
+    }
 }
 
-pub fn get_movement(control: GameControl, input: &Res<Input<KeyCode>>) -> f32 {
-    if control.pressed(input) {
+pub fn get_movement(control: GameControl, keys: &KeyBindings, input: &Res<Input<KeyCode>>) -> f32 {
+    if control.pressed(keys, input) {
         1.0
     } else {
         0.0
@@ -1,37 +1,160 @@
 use bevy::prelude::*;
 
-use crate::actions::game_control::{get_movement, GameControl};
+use crate::actions::game_control::get_movement;
+use crate::menu::GameCfg;
 use crate::GameState;
 
 mod game_control;
 
+pub use crate::actions::game_control::{GameControl, KeyBindings};
+
 pub struct ActionsPlugin;
 
-// This plugin listens for keyboard input and converts the input into Actions
-// Actions can then be used as a resource in other systems to act on the player input.
+// This plugin listens for keyboard and gamepad input and converts the input into PlayerActions
+// PlayerActions can then be used as a resource in other systems to act on the player input.
 impl Plugin for ActionsPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<Actions>()
-            .add_system(set_movement_actions.in_set(OnUpdate(GameState::Game)));
+        app.init_resource::<PlayerActions>()
+            .add_system(set_player_actions.in_set(OnUpdate(GameState::Game)));
     }
 }
 
+/// The input of the player for the current frame, from the keys bound in `KeyBindings` and from
+/// any gamepad
 #[derive(Default, Resource)]
-pub struct Actions {
+pub struct PlayerActions {
+    /// At most of length 1, shorter while a stick is only tilted partway
     pub player_movement: Option<Vec2>,
+    /// Pressed this frame, held buttons don't repeat
+    pub jump: bool,
+    pub interact: bool,
+}
+
+#[deprecated(note = "renamed to `PlayerActions`, apart from the menu `Actions`")]
+pub type Actions = PlayerActions;
+
+/// Stick tilts below it are ignored, on top of the deadzone of the gamepad settings
+const STICK_THRESHOLD: f32 = 0.1;
+
+pub fn set_player_actions(
+    mut actions: ResMut<PlayerActions>,
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    cfg: Res<GameCfg>,
+) {
+    let keys = &cfg.keys;
+    let mut player_movement = Vec2::new(
+        get_movement(GameControl::Right, keys, &keyboard_input)
+            - get_movement(GameControl::Left, keys, &keyboard_input),
+        get_movement(GameControl::Up, keys, &keyboard_input)
+            - get_movement(GameControl::Down, keys, &keyboard_input),
+    )
+    .normalize_or_zero();
+    let button = |gamepad, button_type| GamepadButton::new(gamepad, button_type);
+    let mut jump = keyboard_input.just_pressed(keys.jump);
+    let mut interact = keyboard_input.just_pressed(keys.interact);
+    for gamepad in gamepads.iter() {
+        let axis = |axis_type| {
+            gamepad_axes
+                .get(GamepadAxis::new(gamepad, axis_type))
+                .unwrap_or_default()
+        };
+        let held = |button_type| gamepad_input.pressed(button(gamepad, button_type));
+        let held = |positive, negative| held(positive) as i8 as f32 - held(negative) as i8 as f32;
+        let stick = Vec2::new(
+            axis(GamepadAxisType::LeftStickX),
+            axis(GamepadAxisType::LeftStickY),
+        );
+        let dpad = Vec2::new(
+            held(GamepadButtonType::DPadRight, GamepadButtonType::DPadLeft),
+            held(GamepadButtonType::DPadUp, GamepadButtonType::DPadDown),
+        )
+        .normalize_or_zero();
+        // The keyboard takes precedence, then the D-pad over the stick
+        if player_movement == Vec2::ZERO {
+            player_movement = if dpad != Vec2::ZERO {
+                dpad
+            } else if stick.length() > STICK_THRESHOLD {
+                stick.clamp_length_max(1.)
+            } else {
+                Vec2::ZERO
+            };
+        }
+        jump |= gamepad_input.just_pressed(button(gamepad, GamepadButtonType::South));
+        interact |= gamepad_input.just_pressed(button(gamepad, GamepadButtonType::West));
+    }
+
+    actions.player_movement = (player_movement != Vec2::ZERO).then_some(player_movement);
+    actions.jump = jump;
+    actions.interact = interact;
 }
 
-pub fn set_movement_actions(mut actions: ResMut<Actions>, keyboard_input: Res<Input<KeyCode>>) {
-    let player_movement = Vec2::new(
-        get_movement(GameControl::Right, &keyboard_input)
-            - get_movement(GameControl::Left, &keyboard_input),
-        get_movement(GameControl::Up, &keyboard_input)
-            - get_movement(GameControl::Down, &keyboard_input),
-    );
-
-    if player_movement != Vec2::ZERO {
-        actions.player_movement = Some(player_movement.normalize());
-    } else {
-        actions.player_movement = None;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_resource::<PlayerActions>()
+            .init_resource::<Input<KeyCode>>()
+            .init_resource::<Input<GamepadButton>>()
+            .init_resource::<Axis<GamepadAxis>>()
+            .init_resource::<Gamepads>()
+            .insert_resource(GameCfg::default())
+            .add_system(set_player_actions);
+        app
+    }
+
+    /// Runs a frame with only the `keys` held, newly pressed ones count as just pressed
+    fn hold(app: &mut App, keys: &[KeyCode]) -> &PlayerActions {
+        let mut input = app.world.resource_mut::<Input<KeyCode>>();
+        let released: Vec<_> = input.get_pressed().copied().collect();
+        input.clear();
+        for key in released.iter().filter(|key| !keys.contains(key)) {
+            input.release(*key);
+        }
+        input.clear();
+        for &key in keys {
+            input.press(key);
+        }
+        app.update();
+        app.world.resource::<PlayerActions>()
+    }
+
+    #[test]
+    fn combines_bound_keys_and_arrows() {
+        let mut app = app();
+        assert_eq!(hold(&mut app, &[]).player_movement, None);
+        let movement = hold(&mut app, &[KeyCode::W, KeyCode::Right]).player_movement;
+        assert!(movement.unwrap().abs_diff_eq(Vec2::ONE.normalize(), 1e-6));
+        assert_eq!(
+            hold(&mut app, &[KeyCode::Left]).player_movement,
+            Some(Vec2::NEG_X)
+        );
+        assert_eq!(
+            hold(&mut app, &[KeyCode::A, KeyCode::D]).player_movement,
+            None
+        );
+    }
+
+    #[test]
+    fn follows_rebinding() {
+        let mut app = app();
+        app.world.resource_mut::<GameCfg>().keys.up = KeyCode::I;
+        assert_eq!(hold(&mut app, &[KeyCode::W]).player_movement, None);
+        assert_eq!(hold(&mut app, &[KeyCode::I]).player_movement, Some(Vec2::Y));
+    }
+
+    #[test]
+    fn jumps_and_interacts_once_per_press() {
+        let mut app = app();
+        let actions = hold(&mut app, &[KeyCode::Space, KeyCode::E]);
+        assert!(actions.jump && actions.interact);
+        let actions = hold(&mut app, &[KeyCode::Space, KeyCode::E]);
+        assert!(!actions.jump && !actions.interact);
+        hold(&mut app, &[]);
+        assert!(hold(&mut app, &[KeyCode::Space]).jump);
     }
 }
@@ -1,8 +1,15 @@
-use crate::actions::{set_movement_actions, Actions};
+use crate::actions::{set_player_actions, PlayerActions};
 use crate::loading::AudioAssets;
+use crate::menu::{self, ConfigChanged, GameCfg};
+use crate::rng::GameRng;
 use crate::GameState;
 use bevy::prelude::*;
 use bevy_kira_audio::prelude::*;
+use bevy_quickmenu::QuickMenuComponent;
+use gilrs::ff::{BaseEffect, BaseEffectType, Effect, EffectBuilder, Replay, Ticks};
+use gilrs::Gilrs;
+use rand::Rng;
+use std::time::Duration;
 
 pub struct InternalAudioPlugin;
 
@@ -10,33 +17,383 @@ pub struct InternalAudioPlugin;
 impl Plugin for InternalAudioPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugin(AudioPlugin)
-            .add_system(start_audio.in_schedule(OnEnter(GameState::Game)))
+            .add_audio_channel::<Music>()
+            .init_resource::<MusicCrossfade>()
+            .init_resource::<MenuPlaylist>()
+            .init_resource::<MusicDucking>()
+            .add_event::<SfxPlayed>()
+            .add_event::<AudioUnlocked>()
+            .insert_resource(MusicRng(GameRng::new(0)))
+            .add_system(
+                play_menu_music
+                    .run_if(audio_available)
+                    .run_if(audio_unlocked)
+                    .in_schedule(OnEnter(GameState::Menu)),
+            )
+            // The music held back by `AudioLocked`
+            .add_system(
+                play_menu_music
+                    .run_if(on_event::<AudioUnlocked>())
+                    .in_set(OnUpdate(GameState::Menu)),
+            )
+            .add_system(
+                play_game_music
+                    .run_if(on_event::<AudioUnlocked>())
+                    .in_set(OnUpdate(GameState::Game)),
+            )
+            .add_system(
+                advance_menu_playlist
+                    .run_if(audio_available)
+                    .in_set(OnUpdate(GameState::Menu)),
+            )
+            .add_system(
+                play_game_music
+                    .run_if(audio_available)
+                    .run_if(audio_unlocked)
+                    .in_schedule(OnEnter(GameState::Game)),
+            )
+            .add_system(
+                start_audio
+                    .run_if(audio_available)
+                    .in_schedule(OnEnter(GameState::Game)),
+            )
             .add_system(
                 control_flying_sound
-                    .after(set_movement_actions)
+                    .run_if(audio_available)
+                    .after(set_player_actions)
                     .in_set(OnUpdate(GameState::Game)),
+            )
+            .add_system(update_volume.run_if(audio_available))
+            .add_system(
+                play_click_sound
+                    .run_if(audio_available)
+                    .run_if(audio_unlocked),
+            )
+            .add_system(menu_rumble)
+            .add_system(
+                play_hover_sound
+                    .run_if(audio_available)
+                    .run_if(audio_unlocked),
+            )
+            .add_system(
+                duck_music
+                    .run_if(audio_available)
+                    .after(play_click_sound)
+                    .after(play_hover_sound)
+                    .after(update_volume),
+            )
+            .add_system(
+                detect_missing_audio
+                    .run_if(audio_available)
+                    .run_if(audio_unlocked),
+            );
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            app.insert_resource(AudioLocked).add_system(
+                unlock_audio
+                    .run_if(resource_exists::<AudioLocked>())
+                    .before(play_click_sound)
+                    .before(play_hover_sound),
             );
+        }
     }
 }
 
 #[derive(Resource)]
 struct FlyingAudio(Handle<AudioInstance>);
 
-fn start_audio(mut commands: Commands, audio_assets: Res<AudioAssets>, audio: Res<Audio>) {
+/// Inserted once no sound could be played, without an audio device like on CI or in headless
+/// runs. Every system playing sounds stops then, instead of queueing sounds that never play
+#[derive(Resource)]
+struct AudioUnavailable;
+
+fn audio_available(unavailable: Option<Res<AudioUnavailable>>) -> bool {
+    unavailable.is_none()
+}
+
+/// Browsers suspend the audio until the first user input, when `build/web/sound.js` resumes it.
+/// The music is held back until then, so that it starts from the beginning right after, and the
+/// menu sounds don't pile up. Only inserted on wasm
+#[derive(Resource)]
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+struct AudioLocked;
+
+fn audio_unlocked(locked: Option<Res<AudioLocked>>) -> bool {
+    locked.is_none()
+}
+
+/// Sent once the `AudioLocked` is removed, to start the music of the current state
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+struct AudioUnlocked;
+
+/// Removes the `AudioLocked` on the first menu `Actions` or press of a key, mouse button or touch,
+/// the same inputs the browser resumes the audio on
+#[cfg(target_arch = "wasm32")]
+fn unlock_audio(
+    mut commands: Commands,
+    mut action_event: EventReader<menu::Actions>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    touches: Res<Touches>,
+    mut unlocked_event: EventWriter<AudioUnlocked>,
+) {
+    let interacted = action_event.iter().count() > 0
+        || keyboard_input.get_just_pressed().next().is_some()
+        || mouse_input.get_just_pressed().next().is_some()
+        || touches.iter_just_pressed().next().is_some();
+    if interacted {
+        commands.remove_resource::<AudioLocked>();
+        unlocked_event.send(AudioUnlocked);
+    }
+}
+
+/// Time in seconds the first music gets to start, before the audio is considered unavailable
+const AUDIO_START_TIMEOUT: f32 = 2.;
+
+/// Watches the first music, whose `AudioInstance` only shows up once it is played by a device.
+/// Browsers blocking autoplay still create it and only start the sound later. Without the
+/// `AudioAssets` no music starts at all, which leaves nothing to play either
+fn detect_missing_audio(
+    mut commands: Commands,
+    state: Res<State<GameState>>,
+    current: Option<Res<CurrentMusic>>,
+    audio_instances: Res<Assets<AudioInstance>>,
+    time: Res<Time>,
+    mut started: Local<bool>,
+    mut waited: Local<f32>,
+) {
+    // The music starts after loading
+    if *started || state.0 == GameState::Loading {
+        return;
+    }
+    let current = current.and_then(|current| audio_instances.get(&current.instance));
+    if current.is_some() {
+        *started = true;
+        return;
+    }
+    *waited += time.raw_delta_seconds();
+    if *waited >= AUDIO_START_TIMEOUT {
+        warn!("No audio device available, continuing without sound");
+        commands.insert_resource(AudioUnavailable);
+    }
+}
+
+/// Audio channel for the background music
+#[derive(Resource)]
+pub struct Music;
+
+/// Duration in seconds of the crossfade between the menu and game music
+#[derive(Resource)]
+pub struct MusicCrossfade(pub f32);
+
+impl Default for MusicCrossfade {
+    fn default() -> Self {
+        MusicCrossfade(1.)
+    }
+}
+
+/// How far the music is lowered while a menu sound plays, as a fraction of its volume, and the
+/// time in seconds it takes to come back. Only while `GameCfg::duck_music` is set
+#[derive(Resource)]
+pub struct MusicDucking {
+    pub amount: f32,
+    pub recovery: f32,
+}
+
+impl Default for MusicDucking {
+    fn default() -> Self {
+        MusicDucking {
+            amount: 0.4,
+            recovery: 0.3,
+        }
+    }
+}
+
+/// Sent whenever a menu sound is played
+struct SfxPlayed;
+
+/// Tracks shuffled on the `Music` channel in the menu. While empty, the `AudioAssets::menu_music`
+/// is looped
+#[derive(Resource, Default)]
+pub struct MenuPlaylist(pub Vec<Handle<AudioSource>>);
+
+/// Separate from the `GameRng` of the game, so that the music doesn't change how a seed plays out
+#[derive(Resource)]
+struct MusicRng(GameRng);
+
+/// The track currently played on the `Music` channel
+#[derive(Resource)]
+struct CurrentMusic {
+    track: Handle<AudioSource>,
+    instance: Handle<AudioInstance>,
+}
+
+fn menu_tracks(audio_assets: &AudioAssets, playlist: &MenuPlaylist) -> Vec<Handle<AudioSource>> {
+    if playlist.0.is_empty() {
+        vec![audio_assets.menu_music.clone()]
+    } else {
+        playlist.0.clone()
+    }
+}
+
+/// A random track, other than the `previous` one if there is a choice
+fn shuffle_track(
+    tracks: &[Handle<AudioSource>],
+    previous: Option<&Handle<AudioSource>>,
+    rng: &mut MusicRng,
+) -> Handle<AudioSource> {
+    let candidates: Vec<_> = tracks
+        .iter()
+        .filter(|track| tracks.len() == 1 || Some(*track) != previous)
+        .collect();
+    candidates[rng.0.gen_range(0..candidates.len())].clone()
+}
+
+fn play_menu_music(
+    mut commands: Commands,
+    audio_assets: Option<Res<AudioAssets>>,
+    playlist: Res<MenuPlaylist>,
+    mut rng: ResMut<MusicRng>,
+    music: Res<AudioChannel<Music>>,
+    current: Option<Res<CurrentMusic>>,
+    crossfade: Res<MusicCrossfade>,
+    cfg: Res<GameCfg>,
+) {
+    let Some(audio_assets) = audio_assets else { return };
+    // Pause and GameOver are in `GameState::Menu` too, and keep the menu music playing
+    let tracks = menu_tracks(&audio_assets, &playlist);
+    if current.map_or(false, |current| tracks.contains(&current.track)) {
+        return;
+    }
+    let track = shuffle_track(&tracks, None, &mut rng);
+    let looped = tracks.len() == 1;
+    crossfade_music(&mut commands, &track, looped, &music, &crossfade, &cfg);
+}
+
+/// Shuffles to the next track of the `MenuPlaylist` once the current one finished
+fn advance_menu_playlist(
+    mut commands: Commands,
+    audio_assets: Option<Res<AudioAssets>>,
+    playlist: Res<MenuPlaylist>,
+    mut rng: ResMut<MusicRng>,
+    music: Res<AudioChannel<Music>>,
+    current: Option<Res<CurrentMusic>>,
+    audio_instances: Res<Assets<AudioInstance>>,
+    crossfade: Res<MusicCrossfade>,
+    cfg: Res<GameCfg>,
+) {
+    let Some(audio_assets) = audio_assets else { return };
+    let Some(current) = current else { return };
+    let tracks = menu_tracks(&audio_assets, &playlist);
+    // A single track is looped
+    if tracks.len() < 2 || !tracks.contains(&current.track) {
+        return;
+    }
+    let Some(instance) = audio_instances.get(&current.instance) else { return };
+    if !matches!(instance.state(), PlaybackState::Stopped) {
+        return;
+    }
+    let track = shuffle_track(&tracks, Some(&current.track), &mut rng);
+    crossfade_music(&mut commands, &track, false, &music, &crossfade, &cfg);
+}
+
+fn play_game_music(
+    mut commands: Commands,
+    audio_assets: Option<Res<AudioAssets>>,
+    music: Res<AudioChannel<Music>>,
+    current: Option<Res<CurrentMusic>>,
+    crossfade: Res<MusicCrossfade>,
+    cfg: Res<GameCfg>,
+) {
+    let Some(audio_assets) = audio_assets else { return };
+    let track = &audio_assets.game_music;
+    if current.map_or(true, |current| &current.track != track) {
+        crossfade_music(&mut commands, track, true, &music, &crossfade, &cfg);
+    }
+}
+
+fn crossfade_music(
+    commands: &mut Commands,
+    track: &Handle<AudioSource>,
+    looped: bool,
+    music: &AudioChannel<Music>,
+    crossfade: &MusicCrossfade,
+    cfg: &GameCfg,
+) {
+    let tween = AudioTween::linear(Duration::from_secs_f32(crossfade.0));
+    music.stop().fade_out(tween.clone());
+    music.set_volume(music_volume(cfg));
+    let mut play = music.play(track.clone());
+    if looped {
+        play.looped();
+    }
+    let instance = play.fade_in(tween).handle();
+    commands.insert_resource(CurrentMusic {
+        track: track.clone(),
+        instance,
+    });
+}
+
+/// Volume of the music, silent while `GameCfg::muted`
+fn music_volume(cfg: &GameCfg) -> f64 {
+    if cfg.muted {
+        0.
+    } else {
+        cfg.music_volume as f64
+    }
+}
+
+/// Volume of the sound effects, silent while `GameCfg::muted`. Muting only silences the sounds,
+/// so that they continue where they are when unmuted
+fn sfx_volume(cfg: &GameCfg) -> f64 {
+    if cfg.muted {
+        0.
+    } else {
+        cfg.sfx_volume as f64
+    }
+}
+
+/// Volume of the flying sound at full `GameCfg::sfx_volume`
+const FLYING_VOLUME: f64 = 0.3;
+
+fn start_audio(
+    mut commands: Commands,
+    audio_assets: Option<Res<AudioAssets>>,
+    audio: Res<Audio>,
+    cfg: Res<GameCfg>,
+) {
+    let Some(audio_assets) = audio_assets else { return };
     audio.pause();
     let handle = audio
         .play(audio_assets.flying.clone())
         .looped()
-        .with_volume(0.3)
+        .with_volume(FLYING_VOLUME * sfx_volume(&cfg))
         .handle();
     commands.insert_resource(FlyingAudio(handle));
 }
 
+/// Applies the volumes from `GameCfg` to the playing sounds, so changes are audible right away
+fn update_volume(
+    mut config_event: EventReader<ConfigChanged>,
+    music: Res<AudioChannel<Music>>,
+    audio: Option<Res<FlyingAudio>>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+) {
+    let Some(ConfigChanged { current, .. }) = config_event.iter().last() else { return };
+    music.set_volume(music_volume(current));
+    let Some(audio) = audio else { return };
+    if let Some(instance) = audio_instances.get_mut(&audio.0) {
+        instance.set_volume(FLYING_VOLUME * sfx_volume(current), AudioTween::default());
+    }
+}
+
 fn control_flying_sound(
-    actions: Res<Actions>,
-    audio: Res<FlyingAudio>,
+    actions: Res<PlayerActions>,
+    audio: Option<Res<FlyingAudio>>,
     mut audio_instances: ResMut<Assets<AudioInstance>>,
 ) {
+    let Some(audio) = audio else { return };
     if let Some(instance) = audio_instances.get_mut(&audio.0) {
         match instance.state() {
             PlaybackState::Paused { .. } => {
@@ -53,3 +410,200 @@ fn control_flying_sound(
         }
     }
 }
+
+/// Plays the click sound for the dispatched menu actions, see `Actions::clicks`
+fn play_click_sound(
+    mut action_event: EventReader<menu::Actions>,
+    audio_assets: Option<Res<AudioAssets>>,
+    audio_sources: Res<Assets<AudioSource>>,
+    audio: Res<Audio>,
+    cfg: Res<GameCfg>,
+    mut sfx_event: EventWriter<SfxPlayed>,
+) {
+    if action_event.iter().filter(|action| action.clicks()).count() == 0 {
+        return;
+    }
+    let Some(audio_assets) = audio_assets else { return };
+    if audio_sources.get(&audio_assets.click).is_none() {
+        return;
+    }
+    audio
+        .play(audio_assets.click.clone())
+        .with_volume(sfx_volume(&cfg));
+    sfx_event.send(SfxPlayed);
+}
+
+/// Duration in milliseconds of the rumble confirming a menu action, and of the shorter and weaker
+/// one for going back
+const CONFIRM_RUMBLE_MS: u32 = 200;
+const BACK_RUMBLE_MS: u32 = 80;
+
+/// Strength of the menu rumbles, out of `u16::MAX`, light compared to what a game may play
+const RUMBLE_MAGNITUDE: u16 = 0x6000;
+
+/// Rumbles the connected gamepads for confirming and leaving menu screens, while `Flags::rumble`
+/// is set. The game's own effects are played alongside, as gilrs mixes them per gamepad
+fn menu_rumble(
+    mut action_event: EventReader<menu::Actions>,
+    cfg: Res<GameCfg>,
+    gamepads: Res<Gamepads>,
+    gilrs: Option<NonSendMut<Gilrs>>,
+    // Kept until the next one, as dropping an `Effect` stops it
+    mut effect: Local<Option<Effect>>,
+) {
+    let rumble = action_event
+        .iter()
+        .filter_map(|action| match action {
+            menu::Actions::NewGame | menu::Actions::Resume | menu::Actions::SetNum(_) => {
+                Some((CONFIRM_RUMBLE_MS, true))
+            }
+            menu::Actions::Back => Some((BACK_RUMBLE_MS, false)),
+            _ => None,
+        })
+        .last();
+    let Some((duration, strong)) = rumble.filter(|_| cfg.flags.rumble) else { return };
+    let Some(mut gilrs) = gilrs.filter(|_| gamepads.iter().next().is_some()) else { return };
+    let ids: Vec<_> = gilrs
+        .gamepads()
+        .filter(|(_, gamepad)| gamepad.is_ff_supported())
+        .map(|(id, _)| id)
+        .collect();
+    if ids.is_empty() {
+        return;
+    }
+    let played = EffectBuilder::new()
+        .add_effect(BaseEffect {
+            kind: if strong {
+                BaseEffectType::Strong {
+                    magnitude: RUMBLE_MAGNITUDE,
+                }
+            } else {
+                BaseEffectType::Weak {
+                    magnitude: RUMBLE_MAGNITUDE,
+                }
+            },
+            scheduling: Replay {
+                play_for: Ticks::from_ms(duration),
+                ..default()
+            },
+            ..default()
+        })
+        .gamepads(&ids)
+        .finish(&mut gilrs)
+        .and_then(|played| played.play().map(|_| played));
+    match played {
+        Ok(played) => *effect = Some(played),
+        Err(err) => warn!("Failed to rumble the gamepad: {err}"),
+    }
+}
+
+/// Minimum time in seconds between two hover sounds, so sliding over the menu doesn't spam them
+const HOVER_SOUND_INTERVAL: f64 = 0.08;
+
+/// Plays the hover sound when the mouse enters a menu item. The quickmenu highlights the hovered
+/// item itself
+fn play_hover_sound(
+    interactions: Query<&Interaction, (Changed<Interaction>, With<QuickMenuComponent>)>,
+    mut last_played: Local<f64>,
+    time: Res<Time>,
+    audio_assets: Option<Res<AudioAssets>>,
+    audio_sources: Res<Assets<AudioSource>>,
+    audio: Res<Audio>,
+    cfg: Res<GameCfg>,
+    mut sfx_event: EventWriter<SfxPlayed>,
+) {
+    if !interactions
+        .iter()
+        .any(|interaction| *interaction == Interaction::Hovered)
+    {
+        return;
+    }
+    let now = time.raw_elapsed_seconds_f64();
+    if now - *last_played < HOVER_SOUND_INTERVAL {
+        return;
+    }
+    let Some(audio_assets) = audio_assets else { return };
+    if audio_sources.get(&audio_assets.hover).is_none() {
+        return;
+    }
+    *last_played = now;
+    audio
+        .play(audio_assets.hover.clone())
+        .with_volume(sfx_volume(&cfg));
+    sfx_event.send(SfxPlayed);
+}
+
+/// Lowers the music by the `MusicDucking::amount` when a menu sound is played, and ramps it back
+/// up linearly. The volume from `GameCfg` stays the ceiling, so mute keeps it silent
+fn duck_music(
+    mut sfx_event: EventReader<SfxPlayed>,
+    ducking: Res<MusicDucking>,
+    time: Res<Time>,
+    cfg: Res<GameCfg>,
+    music: Res<AudioChannel<Music>>,
+    mut ducked: Local<f32>,
+) {
+    let amount = ducking.amount.clamp(0., 1.);
+    if sfx_event.iter().count() > 0 && cfg.duck_music {
+        *ducked = amount;
+    } else if *ducked > 0. {
+        // The menu sounds play while the game clock is paused
+        let step = if ducking.recovery > 0. {
+            amount * time.raw_delta_seconds() / ducking.recovery
+        } else {
+            amount
+        };
+        *ducked = (*ducked - step).max(0.);
+    } else {
+        return;
+    }
+    music.set_volume(music_volume(&cfg) * (1. - *ducked) as f64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::input::InputPlugin;
+    use bevy::time::TimeUpdateStrategy;
+
+    const FRAME: Duration = Duration::from_millis(500);
+
+    /// Counts the frames `AudioUnavailable` was inserted in, each time with a warning
+    #[derive(Resource, Default)]
+    struct Warnings(usize);
+
+    fn count_warnings(mut warnings: ResMut<Warnings>) {
+        warnings.0 += 1;
+    }
+
+    #[test]
+    fn menu_continues_without_audio_assets() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(AssetPlugin::default())
+            .add_plugin(InputPlugin)
+            .add_state::<GameState>()
+            .add_event::<menu::Actions>()
+            .add_event::<ConfigChanged>()
+            .init_resource::<GameCfg>()
+            .init_resource::<PlayerActions>()
+            .init_resource::<Warnings>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME))
+            .add_plugin(InternalAudioPlugin)
+            .add_system(count_warnings.run_if(resource_added::<AudioUnavailable>()));
+        app.update();
+        app.world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Menu);
+        for action in [menu::Actions::NewGame, menu::Actions::Back] {
+            app.world.send_event(action);
+            app.update();
+        }
+        // Past the `AUDIO_START_TIMEOUT`, with a frame to spare
+        for _ in 0..=(AUDIO_START_TIMEOUT / FRAME.as_secs_f32()) as usize {
+            app.update();
+        }
+        assert!(app.world.contains_resource::<AudioUnavailable>());
+        assert_eq!(app.world.resource::<Warnings>().0, 1);
+    }
+}
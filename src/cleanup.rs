@@ -0,0 +1,59 @@
+/// Teardown of the game world once a game is over or abandoned. Pausing leaves `GameState::Game`
+/// as well, but keeps the game in the world behind the pause menu
+use crate::menu::{GameCfg, QuitGameEvent, RestartGameEvent, StartGameEvent};
+use crate::save::LoadGameEvent;
+use crate::GameState;
+use bevy::prelude::*;
+
+pub struct CleanupPlugin;
+
+/// This plugin despawns every `GameEntity` when the game ends, either from `GameState::Game` by a
+/// `GameResultEvent`, or from the pause menu by a restart, a new or a loaded game, or a quit to the
+/// main menu
+impl Plugin for CleanupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(despawn_finished_game.in_schedule(OnExit(GameState::Game)))
+            .add_system(despawn_abandoned_game);
+    }
+}
+
+/// Marks the entities of a game, e.g. the player, its enemies and pickups. Children are despawned
+/// with them, so only the top entities need it
+#[derive(Component)]
+pub struct GameEntity;
+
+/// `GameCfg::outcome` is set before the `GameOver` screen is faded in. Any other exit is a pause
+fn despawn_finished_game(
+    mut commands: Commands,
+    cfg: Res<GameCfg>,
+    entities: Query<Entity, With<GameEntity>>,
+) {
+    if cfg.outcome.is_some() {
+        despawn_all(&mut commands, &entities);
+    }
+}
+
+/// Restarts, new and loaded games and quits happen from the menu, after the game was left for the
+/// pause menu. From the `NewGame` or `GameOver` screens they find nothing left
+fn despawn_abandoned_game(
+    mut commands: Commands,
+    mut restart_event: EventReader<RestartGameEvent>,
+    mut start_event: EventReader<StartGameEvent>,
+    mut load_event: EventReader<LoadGameEvent>,
+    mut quit_event: EventReader<QuitGameEvent>,
+    entities: Query<Entity, With<GameEntity>>,
+) {
+    let abandoned = restart_event.iter().count()
+        + start_event.iter().count()
+        + load_event.iter().count()
+        + quit_event.iter().count();
+    if abandoned > 0 {
+        despawn_all(&mut commands, &entities);
+    }
+}
+
+fn despawn_all(commands: &mut Commands, entities: &Query<Entity, With<GameEntity>>) {
+    for entity in entities {
+        commands.entity(entity).despawn_recursive();
+    }
+}
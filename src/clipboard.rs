@@ -0,0 +1,109 @@
+/// Access to the system clipboard, for sharing text like the config. On desktop the clipboard is
+/// kept open, as X11 only serves copied text while its owner lives. The browser clipboard is
+/// asynchronous, so reads are requested and picked up in a later frame on both
+use bevy::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+/// A `NonSend` resource, the clipboard handles aren't `Send`
+#[derive(Default)]
+pub struct Clipboard {
+    #[cfg(not(target_arch = "wasm32"))]
+    clipboard: Option<arboard::Clipboard>,
+    /// The text of the last `request_read`, `None` inside if there was none to read
+    read: Rc<RefCell<Option<Option<String>>>>,
+    /// The callbacks of the pending read, called by the browser once it settles
+    #[cfg(target_arch = "wasm32")]
+    pending: Option<(ReadCallback, ReadCallback)>,
+}
+
+#[cfg(target_arch = "wasm32")]
+type ReadCallback = Closure<dyn FnMut(JsValue)>;
+
+impl Clipboard {
+    /// The text of the last `request_read`, once it is read
+    pub fn take_read(&mut self) -> Option<Option<String>> {
+        let read = self.read.borrow_mut().take();
+        // Both callbacks are done with once one of them was called
+        #[cfg(target_arch = "wasm32")]
+        if read.is_some() {
+            self.pending = None;
+        }
+        read
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Clipboard {
+    /// Opened on first use, failures are logged and retried with the next use
+    fn open(&mut self) -> Option<&mut arboard::Clipboard> {
+        if self.clipboard.is_none() {
+            match arboard::Clipboard::new() {
+                Ok(clipboard) => self.clipboard = Some(clipboard),
+                Err(err) => warn!("Failed to open the clipboard: {err}"),
+            }
+        }
+        self.clipboard.as_mut()
+    }
+
+    /// Returns whether the text was copied, failures are logged
+    pub fn write(&mut self, text: &str) -> bool {
+        let Some(clipboard) = self.open() else { return false };
+        let result = clipboard.set_text(text);
+        if let Err(err) = &result {
+            warn!("Failed to write to the clipboard: {err}");
+        }
+        result.is_ok()
+    }
+
+    /// Read right away on desktop, to be picked up by `take_read` like on wasm
+    pub fn request_read(&mut self) {
+        let text = self.open().and_then(|clipboard| clipboard.get_text().ok());
+        *self.read.borrow_mut() = Some(text);
+    }
+}
+
+/// Calls the `method` of `navigator.clipboard`, which returns a promise. Older browsers and pages
+/// that aren't served over https don't have the clipboard
+#[cfg(target_arch = "wasm32")]
+fn call_clipboard(method: &str, args: &js_sys::Array) -> Option<js_sys::Promise> {
+    let navigator = web_sys::window()?.navigator();
+    let clipboard = js_sys::Reflect::get(&navigator, &"clipboard".into()).ok()?;
+    let method = js_sys::Reflect::get(&clipboard, &method.into()).ok()?;
+    let method: js_sys::Function = method.dyn_into().ok()?;
+    method.apply(&clipboard, args).ok()?.dyn_into().ok()
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Clipboard {
+    /// Returns whether the browser has a clipboard. It only refuses the write without a user
+    /// interaction, and the menu only copies on one
+    pub fn write(&mut self, text: &str) -> bool {
+        let copied = call_clipboard("writeText", &js_sys::Array::of1(&text.into())).is_some();
+        if !copied {
+            warn!("The browser has no clipboard to write to");
+        }
+        copied
+    }
+
+    /// The browser may ask the player for permission first. A read still pending is picked up
+    /// instead of requesting another
+    pub fn request_read(&mut self) {
+        if self.pending.is_some() {
+            return;
+        }
+        let Some(promise) = call_clipboard("readText", &js_sys::Array::new()) else {
+            *self.read.borrow_mut() = Some(None);
+            return;
+        };
+        let (read, failed) = (self.read.clone(), self.read.clone());
+        let on_read =
+            Closure::once(move |text: JsValue| *read.borrow_mut() = Some(text.as_string()));
+        let on_failure = Closure::once(move |_: JsValue| *failed.borrow_mut() = Some(None));
+        let _ = promise.then2(&on_read, &on_failure);
+        // Called by the browser after this returns, so both are kept until `take_read`
+        self.pending = Some((on_read, on_failure));
+    }
+}
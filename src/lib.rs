@@ -1,25 +1,35 @@
-mod actions;
+pub mod actions;
 mod audio;
-mod loading;
-mod menu;
+pub mod cleanup;
+mod clipboard;
+pub mod loading;
+pub mod menu;
 mod player;
+pub mod rng;
+pub mod save;
+mod storage;
+mod transition;
 
 use crate::actions::ActionsPlugin;
 use crate::audio::InternalAudioPlugin;
+use crate::cleanup::CleanupPlugin;
 use crate::loading::LoadingPlugin;
 use crate::menu::MenuPlugin;
 use crate::player::PlayerPlugin;
+use crate::save::SavePlugin;
+use crate::transition::TransitionPlugin;
 
 use bevy::app::App;
+use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
 #[cfg(debug_assertions)]
-use bevy::diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin};
+use bevy::diagnostic::LogDiagnosticsPlugin;
 use bevy::prelude::*;
 
 // This example game uses States to separate logic
 // See https://bevy-cheatbook.github.io/programming/states.html
 // Or https://github.com/bevyengine/bevy/blob/main/examples/ecs/state.rs
-#[derive(States, Default, Clone, Eq, PartialEq, Debug, Hash)]
-enum GameState {
+#[derive(States, Default, Clone, Copy, Eq, PartialEq, Debug, Hash)]
+pub enum GameState {
     // During the loading State the LoadingPlugin will load our assets
     #[default]
     Loading,
@@ -29,21 +39,59 @@ enum GameState {
     Menu,
 }
 
-pub struct GamePlugin;
+/// Sent once for every transition of `GameState`, for plugins that want to react to all of them
+/// instead of adding systems to each `OnEnter` and `OnExit`
+pub struct GameStateChanged {
+    pub from: GameState,
+    pub to: GameState,
+}
+
+fn send_state_changes(
+    state: Res<State<GameState>>,
+    mut previous: Local<Option<GameState>>,
+    mut state_event: EventWriter<GameStateChanged>,
+) {
+    let current = state.0;
+    // The initial state is not a transition
+    if let Some(from) = previous.replace(current) {
+        if from != current {
+            state_event.send(GameStateChanged { from, to: current });
+        }
+    }
+}
+
+/// The whole game. `GamePlugin::default()` plays it with the menu of this template, `with_menu`
+/// swaps in one adapted with the builder methods of `MenuPlugin`
+#[derive(Default)]
+pub struct GamePlugin {
+    menu: MenuPlugin,
+}
+
+impl GamePlugin {
+    pub fn with_menu(menu: MenuPlugin) -> Self {
+        Self { menu }
+    }
+}
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app.add_state::<GameState>()
+            .add_event::<GameStateChanged>()
+            .add_system(send_state_changes)
             .add_plugin(LoadingPlugin)
-            .add_plugin(MenuPlugin)
+            .add_plugin(self.menu.clone())
             .add_plugin(ActionsPlugin)
             .add_plugin(InternalAudioPlugin)
-            .add_plugin(PlayerPlugin);
+            .add_plugin(PlayerPlugin)
+            .add_plugin(CleanupPlugin)
+            .add_plugin(SavePlugin)
+            .add_plugin(TransitionPlugin)
+            // Also needed in release builds, for the FPS overlay
+            .add_plugin(FrameTimeDiagnosticsPlugin::default());
 
         #[cfg(debug_assertions)]
         {
-            app.add_plugin(FrameTimeDiagnosticsPlugin::default())
-                .add_plugin(LogDiagnosticsPlugin::default());
+            app.add_plugin(LogDiagnosticsPlugin::default());
         }
     }
 }
@@ -2,26 +2,108 @@ use crate::GameState;
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
 use bevy_kira_audio::AudioSource;
+use iyes_progress::{ProgressCounter, ProgressPlugin};
 
 pub struct LoadingPlugin;
 
 /// This plugin loads all assets using [`AssetLoader`] from a third party bevy plugin
 /// Alternatively you can write the logic to load assets yourself
 /// If interested, take a look at <https://bevy-cheatbook.github.io/features/assets.html>
+/// While loading, a splash screen with a progress bar is shown
 impl Plugin for LoadingPlugin {
     fn build(&self, app: &mut App) {
-        app.add_loading_state(
-            LoadingState::new(GameState::Loading).continue_to_state(GameState::Menu),
-        )
-        .add_collection_to_loading_state::<_, FontAssets>(GameState::Loading)
-        .add_collection_to_loading_state::<_, AudioAssets>(GameState::Loading)
-        .add_collection_to_loading_state::<_, TextureAssets>(GameState::Loading);
+        app.add_loading_state(LoadingState::new(GameState::Loading))
+            .add_collection_to_loading_state::<_, FontAssets>(GameState::Loading)
+            .add_collection_to_loading_state::<_, AudioAssets>(GameState::Loading)
+            .add_collection_to_loading_state::<_, TextureAssets>(GameState::Loading)
+            .add_plugin(ProgressPlugin::new(GameState::Loading).continue_to(GameState::Menu))
+            .add_system(spawn_splash.in_schedule(OnEnter(GameState::Loading)))
+            .add_system(update_progress_bar.in_set(OnUpdate(GameState::Loading)))
+            .add_system(despawn_splash.in_schedule(OnExit(GameState::Loading)));
+    }
+}
+
+/// Logo shown on the splash screen, loaded on its own as the asset collections are not ready yet
+const SPLASH_LOGO: &str = "textures/bevy.png";
+
+#[derive(Component)]
+struct Splash;
+
+#[derive(Component)]
+struct ProgressBar;
+
+fn spawn_splash(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::BLACK.into(),
+                ..default()
+            },
+            Splash,
+        ))
+        .with_children(|parent| {
+            parent.spawn(ImageBundle {
+                style: Style {
+                    size: Size::new(Val::Px(128.), Val::Px(128.)),
+                    margin: UiRect::bottom(Val::Px(32.)),
+                    ..default()
+                },
+                image: asset_server.load(SPLASH_LOGO).into(),
+                ..default()
+            });
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(300.), Val::Px(16.)),
+                        ..default()
+                    },
+                    background_color: Color::DARK_GRAY.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Percent(0.), Val::Percent(100.)),
+                                ..default()
+                            },
+                            background_color: Color::WHITE.into(),
+                            ..default()
+                        },
+                        ProgressBar,
+                    ));
+                });
+        });
+}
+
+fn update_progress_bar(
+    progress: Option<Res<ProgressCounter>>,
+    mut bar: Query<&mut Style, With<ProgressBar>>,
+) {
+    let Some(progress) = progress else { return };
+    let done: f32 = progress.progress().into();
+    for mut style in &mut bar {
+        style.size.width = Val::Percent(done * 100.);
+    }
+}
+
+fn despawn_splash(mut commands: Commands, splash: Query<Entity, With<Splash>>) {
+    for entity in &splash {
+        commands.entity(entity).despawn_recursive();
     }
 }
 
 // the following asset collections will be loaded during the State `GameState::Loading`
 // when done loading, they will be inserted as resources (see <https://github.com/NiklasEi/bevy_asset_loader>)
 
+/// `fira_sans` is the font of the menu, change it to use your own
 #[derive(AssetCollection, Resource)]
 pub struct FontAssets {
     #[asset(path = "fonts/FiraSans-Bold.ttf")]
@@ -32,10 +114,21 @@ pub struct FontAssets {
 pub struct AudioAssets {
     #[asset(path = "audio/flying.ogg")]
     pub flying: Handle<AudioSource>,
+    #[asset(path = "audio/click.wav")]
+    pub click: Handle<AudioSource>,
+    #[asset(path = "audio/hover.wav")]
+    pub hover: Handle<AudioSource>,
+    #[asset(path = "audio/menu.wav")]
+    pub menu_music: Handle<AudioSource>,
+    #[asset(path = "audio/game.wav")]
+    pub game_music: Handle<AudioSource>,
 }
 
 #[derive(AssetCollection, Resource)]
 pub struct TextureAssets {
     #[asset(path = "textures/bevy.png")]
     pub texture_bevy: Handle<Image>,
+    /// Set as the window icon on desktop
+    #[asset(path = "textures/icon.png")]
+    pub icon: Handle<Image>,
 }
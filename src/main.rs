@@ -2,46 +2,65 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use bevy::prelude::*;
+#[cfg(not(target_arch = "wasm32"))]
 use bevy::window::PrimaryWindow;
+#[cfg(not(target_arch = "wasm32"))]
 use bevy::winit::WinitWindows;
 use bevy::DefaultPlugins;
+#[cfg(not(target_arch = "wasm32"))]
+use bevy_game::loading::TextureAssets;
 use bevy_game::GamePlugin;
-use std::io::Cursor;
+#[cfg(not(target_arch = "wasm32"))]
 use winit::window::Icon;
 
 fn main() {
-    App::new()
-        .insert_resource(Msaa::Off)
+    let mut app = App::new();
+    app.insert_resource(Msaa::Off)
         .insert_resource(ClearColor(Color::rgb(0.4, 0.4, 0.4)))
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
-            primary_window: Some(Window {
-                title: "Bevy game".to_string(), // ToDo
-                resolution: (800., 600.).into(),
-                canvas: Some("#bevy".to_owned()),
-                ..default()
-            }),
-            ..default()
-        }))
-        .add_plugin(GamePlugin)
-        .add_system(set_window_icon.on_startup())
-        .run();
+        .add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: Some(Window {
+                        title: "Bevy game".to_string(), // ToDo
+                        resolution: (800., 600.).into(),
+                        canvas: Some("#bevy".to_owned()),
+                        ..default()
+                    }),
+                    // The menu asks first during a game, see `confirm_window_close`
+                    close_when_requested: cfg!(target_arch = "wasm32"),
+                    ..default()
+                })
+                // Hot-reloads assets like the menu style while developing
+                .set(AssetPlugin {
+                    watch_for_changes: cfg!(all(debug_assertions, not(target_arch = "wasm32"))),
+                    ..default()
+                }),
+        )
+        .add_plugin(GamePlugin::default());
+
+    // The browser tab uses the favicon instead
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        app.add_system(set_window_icon.run_if(resource_added::<TextureAssets>()));
+    }
+
+    app.run();
 }
 
-// Sets the icon on windows and X11
+// Sets the icon on windows and X11, once the `TextureAssets` are loaded
+#[cfg(not(target_arch = "wasm32"))]
 fn set_window_icon(
+    textures: Res<TextureAssets>,
+    images: Res<Assets<Image>>,
     windows: NonSend<WinitWindows>,
     primary_window: Query<Entity, With<PrimaryWindow>>,
 ) {
-    let primary_entity = primary_window.single();
-    let primary = windows.get_window(primary_entity).unwrap();
-    let icon_buf = Cursor::new(include_bytes!(
-        "../build/macos/AppIcon.iconset/icon_256x256.png"
-    ));
-    if let Ok(image) = image::load(icon_buf, image::ImageFormat::Png) {
-        let image = image.into_rgba8();
-        let (width, height) = image.dimensions();
-        let rgba = image.into_raw();
-        let icon = Icon::from_rgba(rgba, width, height).unwrap();
-        primary.set_window_icon(Some(icon));
-    };
+    let Some(image) = images.get(&textures.icon) else { return };
+    let Ok(primary_entity) = primary_window.get_single() else { return };
+    let Some(primary) = windows.get_window(primary_entity) else { return };
+    let size = image.size();
+    match Icon::from_rgba(image.data.clone(), size.x as u32, size.y as u32) {
+        Ok(icon) => primary.set_window_icon(Some(icon)),
+        Err(err) => warn!("Invalid window icon: {err}"),
+    }
 }
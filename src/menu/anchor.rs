@@ -0,0 +1,68 @@
+/// Placement of the menu inside the window
+use bevy::prelude::*;
+
+/// Where the root of the menu sits, see `GameCfg::menu_anchor` and `GameCfg::game_anchor`
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum MenuAnchor {
+    /// Laid out like any other UI root
+    #[default]
+    Flow,
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+/// Alignment of the menu along one axis of the window
+enum Align {
+    Start,
+    Center,
+    End,
+}
+
+impl MenuAnchor {
+    fn alignment(&self) -> Option<(Align, Align)> {
+        use Align::*;
+        match self {
+            Self::Flow => None,
+            Self::TopLeft => Some((Start, Start)),
+            Self::Top => Some((Center, Start)),
+            Self::TopRight => Some((End, Start)),
+            Self::Left => Some((Start, Center)),
+            Self::Center => Some((Center, Center)),
+            Self::Right => Some((End, Center)),
+            Self::BottomLeft => Some((Start, End)),
+            Self::Bottom => Some((Center, End)),
+            Self::BottomRight => Some((End, End)),
+        }
+    }
+
+    /// Positions the root `Style` of the menu absolutely at the anchor. Centering pins both sides
+    /// and lets auto margins split the free space
+    pub fn apply(&self, style: &mut Style) {
+        let Some((horizontal, vertical)) = self.alignment() else { return };
+        style.position_type = PositionType::Absolute;
+        let zero = Val::Px(0.);
+        match horizontal {
+            Align::Start => style.position.left = zero,
+            Align::End => style.position.right = zero,
+            Align::Center => {
+                (style.position.left, style.position.right) = (zero, zero);
+                (style.margin.left, style.margin.right) = (Val::Auto, Val::Auto);
+            }
+        }
+        match vertical {
+            Align::Start => style.position.top = zero,
+            Align::End => style.position.bottom = zero,
+            Align::Center => {
+                (style.position.top, style.position.bottom) = (zero, zero);
+                (style.margin.top, style.margin.bottom) = (Val::Auto, Val::Auto);
+            }
+        }
+    }
+}
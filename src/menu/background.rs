@@ -0,0 +1,100 @@
+/// Background images behind the menu, one per root screen, and the clear color of the window
+use super::{root_screen, GameCfg, MenuTheme, Screens};
+use crate::GameState;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_quickmenu::MenuState;
+
+/// Images shown behind the menu, e.g. title art for `NewGame`. Screens without an image keep the
+/// solid background of the `MenuTheme`. The quickmenu doesn't expose its navigation stack, so
+/// sub-screens keep the image of the screen they were opened from
+#[derive(Resource, Default)]
+pub struct MenuBackgrounds(pub HashMap<Screens, Handle<Image>>);
+
+#[derive(Component)]
+pub struct MenuBackground;
+
+/// Swaps the image whenever the menu is rebuilt at another root screen. The `shown` screen is
+/// the root of the last rebuild, the `MenuState` is replaced rather than added again
+pub fn update_background(
+    mut commands: Commands,
+    menu_state: Option<Res<MenuState<Screens>>>,
+    backgrounds: Option<Res<MenuBackgrounds>>,
+    cfg: Res<GameCfg>,
+    state: Res<State<GameState>>,
+    current: Query<Entity, With<MenuBackground>>,
+    mut shown: Local<Option<Screens>>,
+) {
+    if !menu_state.map_or(false, |menu_state| menu_state.is_changed()) {
+        return;
+    }
+    let screen = root_screen(&state.0, &cfg);
+    if *shown == Some(screen) {
+        return;
+    }
+    *shown = Some(screen);
+    for entity in &current {
+        commands.entity(entity).despawn_recursive();
+    }
+    // The game is drawn instead
+    if screen == Screens::Game {
+        return;
+    }
+    let Some(backgrounds) = backgrounds else { return };
+    let Some(image) = backgrounds.0.get(&screen) else { return };
+    commands.spawn((
+        ImageBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                ..default()
+            },
+            image: image.clone().into(),
+            // Behind the quickmenu root
+            z_index: ZIndex::Global(-1),
+            ..default()
+        },
+        MenuBackground,
+    ));
+}
+
+/// Runs on `OnEnter(GameState::Game)` too, so that the image doesn't wait for the menu rebuild
+pub fn despawn_background(mut commands: Commands, current: Query<Entity, With<MenuBackground>>) {
+    for entity in &current {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// The `ClearColor` of the game, while the `MenuTheme::clear` replaces it
+#[derive(Resource)]
+pub struct GameClearColor(Color);
+
+/// Swaps the `ClearColor` when the menu is opened or closed, or the `MenuTheme` changes. Only the
+/// resource is changed, so the `MenuCamera` clears with it like any other camera. The theme is
+/// inserted with the menu, once loading is done
+pub fn update_clear_color(
+    mut commands: Commands,
+    state: Res<State<GameState>>,
+    cfg: Res<GameCfg>,
+    theme: Option<Res<MenuTheme>>,
+    mut clear_color: ResMut<ClearColor>,
+    game_clear_color: Option<Res<GameClearColor>>,
+) {
+    let Some(theme) = theme else { return };
+    if !state.is_changed() && !theme.is_changed() {
+        return;
+    }
+    let menu_open = state.0 == GameState::Menu && root_screen(&state.0, &cfg) != Screens::Pause;
+    match (theme.clear.filter(|_| menu_open), game_clear_color) {
+        (Some(color), None) => {
+            commands.insert_resource(GameClearColor(clear_color.0));
+            clear_color.0 = color;
+        }
+        (Some(color), Some(_)) => clear_color.0 = color,
+        (None, Some(game_clear_color)) => {
+            clear_color.0 = game_clear_color.0;
+            commands.remove_resource::<GameClearColor>();
+        }
+        (None, None) => (),
+    }
+}
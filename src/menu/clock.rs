@@ -0,0 +1,24 @@
+/// Pauses the game clock while the pause menu is open, so that resuming doesn't make the game jump
+/// ahead. Game systems keep reading `Res<Time>`, which stands still during the pause, while the
+/// menu itself animates with the `raw_` times
+use super::{root_screen, GameCfg, Screens};
+use crate::GameState;
+use bevy::prelude::*;
+
+/// Pauses `Time` when the `Pause` screen is opened. The `NewGame` and `GameOver` screens keep it
+/// running, as there is no game in progress to hold
+pub fn pause_game_clock(mut time: ResMut<Time>, state: Res<State<GameState>>, cfg: Res<GameCfg>) {
+    if root_screen(&state.0, &cfg) == Screens::Pause {
+        time.pause();
+    }
+}
+
+pub fn unpause_game_clock(mut time: ResMut<Time>) {
+    time.unpause();
+}
+
+/// Run condition for game systems that don't run in `OnUpdate(GameState::Game)` but should still
+/// stand still during a pause, e.g. `.add_system(tick_enemies.run_if(game_clock_running))`
+pub fn game_clock_running(time: Res<Time>) -> bool {
+    !time.is_paused()
+}
@@ -0,0 +1,182 @@
+/// Persistence of `GameCfg` between sessions. The configuration is stored as RON in
+/// `config.ron` inside the platform config dir, or in `localStorage` on wasm.
+use super::{ConfigChanged, CustomScreens, DefaultConfig, DisplayMode, FlagLabels, GameCfg};
+use crate::{save, storage};
+use bevy::app::AppExit;
+use bevy::prelude::*;
+
+const CONFIG_FILE: &str = "config.ron";
+
+/// Bump this whenever the meaning of a field changes, and add a step to `migrate`
+pub const CONFIG_VERSION: u32 = 3;
+
+/// Configs written before the `version` field was added
+pub fn unversioned() -> u32 {
+    1
+}
+
+/// Brings a config written by an older version up to date. Fields missing from it already get
+/// their defaults from `#[serde(default)]`, so steps are only needed for changed fields. The
+/// migrated config is written back with the next change
+pub fn migrate(cfg: &mut GameCfg) {
+    // Version 2 only added fields
+    if cfg.version < 3 && cfg.fullscreen {
+        cfg.display_mode = DisplayMode::Fullscreen;
+    }
+    if cfg.version < CONFIG_VERSION {
+        info!("Migrated {CONFIG_FILE} from version {}", cfg.version);
+        cfg.version = CONFIG_VERSION;
+    }
+}
+
+/// Reads the stored `GameCfg` and inserts it. A missing or malformed config falls back to the
+/// `DefaultConfig`, fields missing from a stored one to `GameCfg::default()`
+pub fn load_config(
+    mut commands: Commands,
+    defaults: Res<DefaultConfig>,
+    custom_screens: Option<Res<CustomScreens>>,
+    flag_labels: Option<Res<FlagLabels>>,
+) {
+    let contents = storage::read(CONFIG_FILE);
+    let first_launch = contents.is_none();
+    let mut cfg: GameCfg = contents
+        .and_then(|contents| match ron::from_str::<GameCfg>(&contents) {
+            Ok(mut cfg) => {
+                migrate(&mut cfg);
+                Some(cfg)
+            }
+            Err(err) => {
+                warn!("Ignoring malformed {CONFIG_FILE}: {err}");
+                None
+            }
+        })
+        .map_or(defaults.0, |cfg| cfg.with_game_fields(&defaults.0));
+    cfg.sanitize();
+    cfg.seen_welcome &= !first_launch;
+    cfg.saves = save::timestamps();
+    // Leaked once, as the `GameCfg` is `Copy` to be shared with the quickmenu
+    if let Some(custom_screens) = custom_screens {
+        cfg.custom_screens = Box::leak(custom_screens.0.clone().into_boxed_slice());
+    }
+    if let Some(flag_labels) = flag_labels {
+        cfg.flag_labels = flag_labels.0;
+    }
+
+    commands.insert_resource(cfg);
+}
+
+fn write_config(cfg: &GameCfg) {
+    match ron::ser::to_string_pretty(cfg, default()) {
+        Ok(contents) => {
+            storage::write(CONFIG_FILE, &contents);
+        }
+        Err(err) => warn!("Failed to serialize {CONFIG_FILE}: {err}"),
+    }
+}
+
+/// Time in seconds changes of the config are collected, before they are written at once
+#[derive(Resource)]
+pub struct ConfigSaveDelay(pub f32);
+
+impl Default for ConfigSaveDelay {
+    fn default() -> Self {
+        ConfigSaveDelay(0.5)
+    }
+}
+
+/// Writes the config when it is changed from the menu, at most once per `ConfigSaveDelay`, so that
+/// rapid toggling doesn't hammer the disk. Writes to `localStorage` are cheap and there is no exit
+/// to flush on, so wasm builds write right away
+pub fn save_config(
+    mut config_event: EventReader<ConfigChanged>,
+    delay: Res<ConfigSaveDelay>,
+    time: Res<Time>,
+    mut pending: Local<Option<(GameCfg, Timer)>>,
+) {
+    if let Some(event) = config_event.iter().last() {
+        if cfg!(target_arch = "wasm32") {
+            write_config(&event.current);
+            return;
+        }
+        match pending.as_mut() {
+            Some((cfg, _)) => *cfg = event.current,
+            None => {
+                let timer = Timer::from_seconds(delay.0, TimerMode::Once);
+                *pending = Some((event.current, timer));
+            }
+        }
+    }
+    let Some((cfg, timer)) = pending.as_mut() else { return };
+    if timer.tick(time.raw_delta()).finished() {
+        write_config(cfg);
+        *pending = None;
+    }
+}
+
+/// Writes the config once more when the app exits, by `Actions::Quit` or by closing the window, so
+/// that changes still waiting for the `ConfigSaveDelay` are kept. Runs in `CoreSet::Last`, after
+/// every system that may have changed it
+pub fn flush_config(mut exit_event: EventReader<AppExit>, cfg: Option<Res<GameCfg>>) {
+    if exit_event.iter().count() == 0 {
+        return;
+    }
+    if let Some(cfg) = cfg {
+        write_config(&cfg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::KeyBindings;
+    use bevy::utils::{Duration, Instant};
+
+    #[test]
+    fn migrates_unversioned_configs() {
+        let mut cfg: GameCfg = ron::from_str("(num: 5, fullscreen: true)").unwrap();
+        assert_eq!(cfg.version, 1);
+        migrate(&mut cfg);
+        assert_eq!(cfg.version, CONFIG_VERSION);
+        assert_eq!(cfg.num, 5);
+        assert_eq!(cfg.display_mode, DisplayMode::Fullscreen);
+        // Added after the first version
+        assert_eq!(cfg.keys, KeyBindings::default());
+        assert!(cfg.duck_music);
+    }
+
+    /// Runs a frame `seconds` after `start`
+    fn update_at(app: &mut App, start: Instant, seconds: f32) {
+        let instant = start + Duration::from_secs_f32(seconds);
+        app.world
+            .resource_mut::<Time>()
+            .update_with_instant(instant);
+        app.update();
+    }
+
+    #[test]
+    fn coalesces_config_writes() {
+        storage::clear();
+        let mut app = App::new();
+        app.add_event::<ConfigChanged>()
+            .insert_resource(ConfigSaveDelay(0.5))
+            .init_resource::<Time>()
+            .add_system(save_config);
+        let start = Instant::now();
+        for num in 0..10 {
+            let previous = GameCfg::default();
+            let mut current = previous;
+            current.num = num;
+            let mut config_event = app.world.resource_mut::<Events<ConfigChanged>>();
+            config_event.send(ConfigChanged { previous, current });
+            update_at(&mut app, start, num as f32 * 0.01);
+        }
+        update_at(&mut app, start, 0.3);
+        assert_eq!(storage::read(CONFIG_FILE), None);
+        update_at(&mut app, start, 0.6);
+        let written = storage::read(CONFIG_FILE).expect("the config is written after the delay");
+        assert_eq!(ron::from_str::<GameCfg>(&written).unwrap().num, 9);
+        storage::clear();
+        update_at(&mut app, start, 1.5);
+        assert_eq!(storage::read(CONFIG_FILE), None);
+    }
+}
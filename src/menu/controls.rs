@@ -0,0 +1,40 @@
+/// Rebinding of the `GameControl` keys from the `Controls` screen
+use super::{ConfigChanged, GameCfg, Screens};
+use crate::actions::GameControl;
+use bevy::prelude::*;
+use bevy_quickmenu::MenuState;
+
+/// Binds the next pressed key to the `GameControl` selected on the `Controls` screen
+/// Escape is only accepted for `Pause`, so that the menu can always be left
+pub fn capture_rebinding(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut armed: Local<bool>,
+    mut config_event: EventWriter<ConfigChanged>,
+    mut cfg: ResMut<GameCfg>,
+    menu_state: Option<ResMut<MenuState<Screens>>>,
+) {
+    let Some(mut menu_state) = menu_state else { return };
+    let Some(control) = menu_state.state().rebinding else {
+        *armed = false;
+        return;
+    };
+    // Skips the frame the entry was selected in, its key would be bound otherwise
+    if !*armed {
+        *armed = true;
+        return;
+    }
+    let Some(&key) = keyboard_input.get_just_pressed().next() else { return };
+    let previous = *cfg;
+    let state = menu_state.state_mut();
+    state.rebinding = None;
+    if key == KeyCode::Escape && control != GameControl::Pause {
+        warn!("Escape can only be bound to Pause");
+    } else {
+        state.keys.set(control, key);
+    }
+    cfg.update_from_menu(state);
+    config_event.send(ConfigChanged {
+        previous,
+        current: *cfg,
+    });
+}
@@ -0,0 +1,57 @@
+/// Screens and actions registered by the game, without adding them to `Screens` and `Actions`
+use super::{Actions, GameCfg, Screens};
+use bevy::prelude::*;
+use bevy_quickmenu::MenuItem;
+
+/// Builds the items of a custom screen, like `ScreenTrait::resolve` of the built-in ones
+pub type ScreenBuilder = fn(&GameCfg) -> Vec<MenuItem<Screens>>;
+
+/// A screen registered with `RegisterScreen::register_screen`, shown as `Screens::Custom(id)`
+#[derive(Clone, Copy)]
+pub struct CustomScreen {
+    /// Also the label of the entry opening it on the `NewGame` and `Pause` screens
+    pub id: &'static str,
+    pub build: ScreenBuilder,
+}
+
+/// The registered screens, handed to `GameCfg::custom_screens` when the config is loaded
+#[derive(Resource, Default)]
+pub struct CustomScreens(pub Vec<CustomScreen>);
+
+/// Lets games add their own screens, e.g. a leaderboard:
+/// `app.register_screen("Leaderboard", |cfg| vec![MenuItem::headline("Leaderboard"), ..])`
+/// The builder gets the `GameCfg`, the only state the quickmenu hands to screens, so the data it
+/// shows has to be reachable from there. Its items can use `Actions::Back` to return, and
+/// `Actions::CustomAction` for buttons of the game, see `CustomMenuEvent`
+pub trait RegisterScreen {
+    fn register_screen(&mut self, id: &'static str, build: ScreenBuilder) -> &mut Self;
+}
+
+impl RegisterScreen for App {
+    fn register_screen(&mut self, id: &'static str, build: ScreenBuilder) -> &mut Self {
+        self.init_resource::<CustomScreens>()
+            .world
+            .resource_mut::<CustomScreens>()
+            .0
+            .push(CustomScreen { id, build });
+        self
+    }
+}
+
+/// Sent for every `Actions::CustomAction` activated in the menu, with its id. Game plugins read
+/// it with an `EventReader<CustomMenuEvent>` and match on the ids of their buttons. Ids should be
+/// prefixed by the plugin, e.g. `"shop:open"`, to keep the ones of different plugins apart. They
+/// aren't shown, so the label of the button is translated on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CustomMenuEvent(pub &'static str);
+
+pub fn forward_custom_actions(
+    mut action_event: EventReader<Actions>,
+    mut custom_event: EventWriter<CustomMenuEvent>,
+) {
+    for event in action_event.iter() {
+        if let Actions::CustomAction(id) = event {
+            custom_event.send(CustomMenuEvent(id));
+        }
+    }
+}
@@ -0,0 +1,55 @@
+/// Overlay listing the live `GameCfg` values in the bottom left corner, to check that the menu
+/// actions change them. Only compiled into debug builds, and toggled with `DEBUG_OVERLAY_KEY`
+use super::overlay::{toggle_overlay_text, OverlayText};
+use super::GameCfg;
+use crate::loading::FontAssets;
+use bevy::prelude::*;
+
+const DEBUG_OVERLAY_KEY: KeyCode = KeyCode::F3;
+
+/// Whether the overlay is shown
+#[derive(Resource, Default)]
+pub struct DebugOverlay(pub bool);
+
+#[derive(Component)]
+pub struct DebugText;
+
+pub fn toggle_debug_overlay(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut debug_overlay: ResMut<DebugOverlay>,
+    fonts: Option<Res<FontAssets>>,
+    overlay: Query<Entity, With<DebugText>>,
+) {
+    if keyboard_input.just_pressed(DEBUG_OVERLAY_KEY) {
+        debug_overlay.0 ^= true;
+    }
+    toggle_overlay_text(
+        &mut commands,
+        debug_overlay.0,
+        overlay.get_single().ok(),
+        fonts.as_deref(),
+        OverlayText {
+            font_size: 16.,
+            color: Color::YELLOW,
+            position: UiRect {
+                left: Val::Px(8.),
+                bottom: Val::Px(8.),
+                ..default()
+            },
+        },
+        DebugText,
+    );
+}
+
+/// Rewrites the text when the `GameCfg` changes, or when the overlay was just spawned
+pub fn update_debug_text(cfg: Res<GameCfg>, mut text: Query<(&mut Text, Ref<DebugText>)>) {
+    let Ok((mut text, overlay)) = text.get_single_mut() else { return };
+    if !cfg.is_changed() && !overlay.is_added() {
+        return;
+    }
+    text.sections[0].value = format!(
+        "boolean: {}\nnum: {}\nmusic volume: {:.2}\nsfx volume: {:.2}\noutcome: {:?}",
+        cfg.flags.boolean, cfg.num, cfg.music_volume, cfg.sfx_volume, cfg.outcome
+    );
+}
@@ -0,0 +1,57 @@
+/// Difficulty presets, each setting a cluster of `GameCfg` fields at once
+use super::GameCfg;
+use serde::{Deserialize, Serialize};
+
+/// Selected on the `Difficulty` screen. The values stay editable on their own, e.g. on the `Num`
+/// screen, so the config only has a difficulty while it matches one of the presets
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Self; 3] = [Self::Easy, Self::Normal, Self::Hard];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Easy => "Easy",
+            Self::Normal => "Normal",
+            Self::Hard => "Hard",
+        }
+    }
+    /// `num` and `speed` of the preset, `Normal` matches the defaults. Each `num` is distinct and
+    /// rises with the difficulty, see `from_num`. `Easy` is below the default `num_min`, so there
+    /// it only differs from `Normal` in `speed`
+    fn values(&self) -> (u8, f32) {
+        match self {
+            Self::Easy => (2, 0.75),
+            Self::Normal => (3, 1.),
+            Self::Hard => (5, 1.5),
+        }
+    }
+    /// The `values` in the `num_min..=num_max` range of `cfg`, as the menu offers no others
+    fn values_in(&self, cfg: &GameCfg) -> (u8, f32) {
+        let (num, speed) = self.values();
+        (num.clamp(cfg.num_min, cfg.num_max), speed)
+    }
+    pub fn apply(&self, cfg: &mut GameCfg) {
+        (cfg.num, cfg.speed) = self.values_in(cfg);
+    }
+    /// The preset matching the config, `None` for a custom one
+    pub fn of(cfg: &GameCfg) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.values_in(cfg) == (cfg.num, cfg.speed))
+    }
+    /// The difficulty of a custom `num` of `cfg`, for game code that needs one either way. That is
+    /// the hardest preset with at most that `num`
+    pub fn from_num(cfg: &GameCfg) -> Self {
+        Self::ALL
+            .into_iter()
+            .rev()
+            .find(|difficulty| difficulty.values_in(cfg).0 <= cfg.num)
+            .unwrap_or(Self::Easy)
+    }
+}
@@ -0,0 +1,46 @@
+/// Overlay showing the frame rate in the top left corner, while `Flags::show_fps` is set
+use super::overlay::{toggle_overlay_text, OverlayText};
+use super::GameCfg;
+use crate::loading::FontAssets;
+use bevy::diagnostic::{Diagnostics, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct FpsText;
+
+/// Spawns or despawns the overlay, following the flag
+pub fn toggle_fps_overlay(
+    mut commands: Commands,
+    cfg: Res<GameCfg>,
+    fonts: Option<Res<FontAssets>>,
+    overlay: Query<Entity, With<FpsText>>,
+) {
+    toggle_overlay_text(
+        &mut commands,
+        cfg.flags.show_fps,
+        overlay.get_single().ok(),
+        fonts.as_deref(),
+        OverlayText {
+            font_size: 20.,
+            color: Color::WHITE,
+            position: UiRect {
+                left: Val::Px(8.),
+                top: Val::Px(8.),
+                ..default()
+            },
+        },
+        FpsText,
+    );
+}
+
+pub fn update_fps_text(diagnostics: Res<Diagnostics>, mut text: Query<&mut Text, With<FpsText>>) {
+    let Ok(mut text) = text.get_single_mut() else { return };
+    let fps = diagnostics.get(FrameTimeDiagnosticsPlugin::FPS);
+    let frame_time = diagnostics.get(FrameTimeDiagnosticsPlugin::FRAME_TIME);
+    if let (Some(fps), Some(frame_time)) = (
+        fps.and_then(|fps| fps.smoothed()),
+        frame_time.and_then(|frame_time| frame_time.smoothed()),
+    ) {
+        text.sections[0].value = format!("{fps:.0} FPS / {frame_time:.2} ms");
+    }
+}
@@ -0,0 +1,150 @@
+/// Translation tables for the menu strings. The keys are the English strings, so a missing
+/// translation falls back to English
+use serde::{Deserialize, Serialize};
+
+/// A translation table from the English menu strings to one language
+pub struct Localization {
+    /// Name of the language, in that language
+    pub name: &'static str,
+    pub strings: &'static [(&'static str, &'static str)],
+}
+
+impl Localization {
+    pub fn tr(&self, key: &'static str) -> &'static str {
+        self.strings
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map_or(key, |(_, value)| value)
+    }
+}
+
+pub const ENGLISH: Localization = Localization {
+    name: "English",
+    strings: &[],
+};
+
+pub const GERMAN: Localization = Localization {
+    name: "Deutsch",
+    strings: &[
+        ("Paused", "Pausiert"),
+        ("Resume", "Fortsetzen"),
+        ("Restart", "Neustarten"),
+        ("Retry", "Nochmal versuchen"),
+        ("New Game", "Neues Spiel"),
+        ("Settings", "Einstellungen"),
+        ("Quit", "Beenden"),
+        ("Pause", "Pause"),
+        ("You Win!", "Gewonnen!"),
+        ("You Lose", "Verloren"),
+        ("Game Over", "Spiel vorbei"),
+        ("Victory", "Sieg"),
+        ("Defeat", "Niederlage"),
+        ("Start a New Game", "Neues Spiel starten"),
+        ("Credits", "Mitwirkende"),
+        ("Audio", "Audio"),
+        ("Music", "Musik"),
+        ("SFX", "Effekte"),
+        ("Mute", "Stumm"),
+        ("Duck Music", "Musik absenken"),
+        ("Fullscreen", "Vollbild"),
+        ("Window Mode", "Fenstermodus"),
+        ("Windowed", "Fenster"),
+        ("Fit to Screen", "Bildschirmfüllend"),
+        ("Resolution", "Auflösung"),
+        ("Language", "Sprache"),
+        ("UI Scale", "UI-Skalierung"),
+        ("VSync", "VSync"),
+        ("On", "An"),
+        ("Off", "Aus"),
+        ("Fast", "Schnell"),
+        ("Controls", "Steuerung"),
+        ("press a key", "Taste drücken"),
+        ("Up", "Hoch"),
+        ("Down", "Runter"),
+        ("Left", "Links"),
+        ("Right", "Rechts"),
+        ("Jump", "Springen"),
+        ("Interact", "Interagieren"),
+        ("Difficulty", "Schwierigkeit"),
+        ("Easy", "Leicht"),
+        ("Normal", "Normal"),
+        ("Hard", "Schwer"),
+        ("Custom", "Benutzerdefiniert"),
+        ("Seed", "Startwert"),
+        ("Random", "Zufällig"),
+        ("Replay", "Wiederholen"),
+        ("Theme", "Farbschema"),
+        ("Dark", "Dunkel"),
+        ("Light", "Hell"),
+        ("Colorblind Mode", "Farbenblind-Modus"),
+        ("Share Usage Data", "Nutzungsdaten teilen"),
+        ("Quit to Main Menu", "Zum Hauptmenü"),
+        ("Remember Menu Position", "Menüposition merken"),
+        ("Help", "Hilfe"),
+        ("Welcome to", "Willkommen bei"),
+        (
+            "Fly with WASD or the arrow keys",
+            "Fliege mit WASD oder den Pfeiltasten",
+        ),
+        ("Escape pauses the game", "Escape pausiert das Spiel"),
+        ("Got it", "Verstanden"),
+        (
+            "Saves to the autosave slot whenever the game is paused",
+            "Speichert bei jeder Pause im Autosave-Platz",
+        ),
+        (
+            "Highlights the focused item by contrast instead of color",
+            "Hebt den ausgewählten Eintrag durch Kontrast statt Farbe hervor",
+        ),
+        (
+            "Reports the menu actions to the game, off by default",
+            "Meldet die Menüaktionen an das Spiel, standardmäßig aus",
+        ),
+        (
+            "A borderless window covering the monitor",
+            "Ein randloses Fenster über den ganzen Bildschirm",
+        ),
+        (
+            "0 picks a random seed for every new game",
+            "0 wählt für jedes neue Spiel einen zufälligen Seed",
+        ),
+        ("Arrow keys also move", "Pfeiltasten bewegen ebenfalls"),
+        ("Menu", "Menü"),
+        ("Space or Enter to select", "Leertaste/Enter wählt aus"),
+        ("Reset to Defaults", "Zurücksetzen"),
+        ("Export Config", "Einstellungen exportieren"),
+        ("Import Config", "Einstellungen importieren"),
+        ("Clipboard unavailable", "Zwischenablage nicht verfügbar"),
+        ("Invalid shared config", "Ungültige Einstellungen"),
+        ("Save Game", "Spiel speichern"),
+        ("Load Game", "Spiel laden"),
+        ("Slot", "Platz"),
+        ("Empty", "Leer"),
+        ("Autosave", "Automatisch speichern"),
+        ("Back", "Zurück"),
+        ("Discard the current game?", "Aktuelles Spiel verwerfen?"),
+        ("Yes, Start Over", "Ja, neu beginnen"),
+        ("Quit?", "Beenden?"),
+        ("Yes, Quit", "Ja, beenden"),
+        ("No, Cancel", "Nein, abbrechen"),
+    ],
+};
+
+/// Language of the menu, selected on the `Language` screen
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Self; 2] = [Self::English, Self::German];
+
+    pub fn localization(&self) -> &'static Localization {
+        match self {
+            Self::English => &ENGLISH,
+            Self::German => &GERMAN,
+        }
+    }
+}
@@ -0,0 +1,86 @@
+/// An animated logo at the top of the `NewGame` screen
+use super::{root_screen, GameCfg, Screens};
+use crate::loading::TextureAssets;
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_quickmenu::MenuState;
+
+/// Size of the logo in logical pixels
+const LOGO_SIZE: f32 = 96.;
+
+/// Distance of the logo to the top of the window, around which it bobs
+const LOGO_TOP: f32 = 24.;
+
+/// How far the logo bobs up and down, and how fast, per second
+const BOB_HEIGHT: f32 = 6.;
+const BOB_SPEED: f32 = 2.;
+
+#[derive(Component)]
+pub struct TitleLogo;
+
+/// Spawns the logo when the menu is built at `NewGame`, and despawns it for any other root screen.
+/// Sub-screens open beside `NewGame` and keep it visible, so the logo stays with them. The `shown`
+/// screen is the root of the last rebuild, like for the `MenuBackground`
+pub fn update_title_logo(
+    mut commands: Commands,
+    menu_state: Option<Res<MenuState<Screens>>>,
+    cfg: Res<GameCfg>,
+    state: Res<State<GameState>>,
+    textures: Option<Res<TextureAssets>>,
+    logo: Query<Entity, With<TitleLogo>>,
+    mut shown: Local<Option<Screens>>,
+) {
+    if !menu_state.map_or(false, |menu_state| menu_state.is_changed()) {
+        return;
+    }
+    let screen = root_screen(&state.0, &cfg);
+    if *shown == Some(screen) {
+        return;
+    }
+    *shown = Some(screen);
+    for entity in &logo {
+        commands.entity(entity).despawn_recursive();
+    }
+    let Some(textures) = textures else { return };
+    if screen != Screens::NewGame {
+        return;
+    }
+    commands.spawn((
+        ImageBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    left: Val::Percent(50.),
+                    top: Val::Px(LOGO_TOP),
+                    ..default()
+                },
+                margin: UiRect::left(Val::Px(-LOGO_SIZE / 2.)),
+                size: Size::new(Val::Px(LOGO_SIZE), Val::Px(LOGO_SIZE)),
+                ..default()
+            },
+            image: textures.texture_bevy.clone().into(),
+            z_index: ZIndex::Global(1),
+            ..default()
+        },
+        TitleLogo,
+    ));
+}
+
+/// Runs on `OnEnter(GameState::Game)`, so that the logo doesn't wait for the menu rebuild
+pub fn despawn_title_logo(mut commands: Commands, logo: Query<Entity, With<TitleLogo>>) {
+    for entity in &logo {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Bobs the logo and pulses its opacity in step
+pub fn animate_title_logo(
+    time: Res<Time>,
+    mut logo: Query<(&mut Style, &mut BackgroundColor), With<TitleLogo>>,
+) {
+    let phase = (time.raw_elapsed_seconds() * BOB_SPEED).sin();
+    for (mut style, mut color) in &mut logo {
+        style.position.top = Val::Px(LOGO_TOP + phase * BOB_HEIGHT);
+        color.0.set_a(0.85 + 0.15 * phase);
+    }
+}
@@ -0,0 +1,1506 @@
+/// Menu is based on `bevy_quickmenu` with `Screens` and `Actions` around YourGame Configuration
+/// struct `GameCfg`
+use crate::actions::{GameControl, KeyBindings};
+use crate::clipboard::Clipboard;
+use crate::loading::FontAssets;
+use crate::rng::GameRng;
+use crate::save::{
+    self, LoadGameEvent, SaveGameEvent, SaveSet, SaveSlot, AUTOSAVE_SLOT, SAVE_SLOTS,
+};
+use crate::transition::FadeTransition;
+use crate::GameState;
+use bevy::ecs::system::SystemParam;
+use bevy::ui::UiSystem;
+use bevy::window::{PresentMode, PrimaryWindow};
+use bevy::{app::AppExit, prelude::*};
+use bevy_quickmenu::{style::Stylesheet, *};
+use serde::{Deserialize, Serialize};
+
+mod anchor;
+mod background;
+mod clock;
+mod config;
+mod controls;
+mod custom;
+#[cfg(debug_assertions)]
+mod debug;
+mod difficulty;
+mod fps;
+mod localization;
+mod logo;
+mod navigation;
+mod observer;
+mod overlay;
+mod saves;
+mod scroll;
+mod screen;
+mod share;
+mod shortcut;
+mod slider;
+#[cfg(debug_assertions)]
+mod style;
+#[cfg(test)]
+pub(crate) mod tests;
+mod theme;
+mod tooltip;
+mod touch;
+mod window;
+
+pub use anchor::MenuAnchor;
+pub use background::MenuBackgrounds;
+pub use clock::game_clock_running;
+pub use config::ConfigSaveDelay;
+pub use custom::{CustomMenuEvent, CustomScreen, CustomScreens, RegisterScreen, ScreenBuilder};
+pub use difficulty::Difficulty;
+pub use localization::Language;
+pub use observer::{AddMenuObserver, MenuObserver, MenuObservers};
+pub use theme::{MenuTheme, Theme};
+pub use window::DisplayMode;
+
+use shortcut::with_key;
+
+/// `Screens` will hold different menu structures. This decides what will be shown in the menu
+/// panel. Atleast one of them will be present at any given time.
+/// - During `GameState::Game` `Game` screen will be active
+/// - During `GameState::Menu`
+///   - `NewGame` at the start of the game
+///   - `Pause` when a game is still going on in background
+///   - `GameOver` when a game is over
+/// - During any state, and for some of the screens, sub-screens like `Settings` and `Num` might be
+///   active
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Screens {
+    Game,
+    Pause,
+    NewGame,
+    GameOver,
+    /// Shown once instead of `NewGame`, on the first launch
+    Welcome,
+    /// Sub screens
+    Settings,
+    Difficulty,
+    Seed,
+    Num,
+    Audio,
+    Controls,
+    /// Read-only list of the bound keys
+    Help,
+    Language,
+    Theme,
+    DisplayMode,
+    Credits,
+    ConfirmNewGame,
+    SaveGame,
+    LoadGame,
+    #[cfg(not(target_arch = "wasm32"))]
+    ConfirmQuit,
+    #[cfg(not(target_arch = "wasm32"))]
+    Resolution,
+    /// A screen registered by the game, by its id
+    Custom(&'static str),
+    UiScale,
+}
+
+/// `Actions` will hold button actions
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Actions {
+    Resume,
+    Pause,
+    #[cfg(not(target_arch = "wasm32"))]
+    Quit,
+    NewGame,
+    /// Closes the `Welcome` screen for good
+    DismissWelcome,
+    /// Starts the current game over with the same seed, see `RestartGameEvent`. Offered as Retry
+    /// on the `GameOver` screen
+    Restart,
+    /// Abandons the current game for the `NewGame` screen, staying in the menu, see
+    /// `QuitGameEvent`
+    QuitToMenu,
+    /// Pops the current sub-screen, returning to the screen it was opened from
+    Back,
+    /// Toggles one of the `Flags`, by its name
+    SetFlag(&'static str),
+    SetNum(u8),
+    /// Steps `num` by one, stopping at `num_min` and `num_max`
+    IncNum,
+    DecNum,
+    /// Sets the values of the preset
+    SetDifficulty(Difficulty),
+    /// Seed of the next games, 0 for a random one
+    SetSeed(u64),
+    /// Volumes are set in percent
+    SetMusicVolume(u8),
+    SetSfxVolume(u8),
+    SetDisplayMode(DisplayMode),
+    ToggleAutosave,
+    ToggleMute,
+    ToggleDuckMusic,
+    ToggleColorblindMode,
+    ToggleShareUsageData,
+    ToggleRememberMenuPosition,
+    #[cfg(not(target_arch = "wasm32"))]
+    SetResolution(u16, u16),
+    /// Switches to the next of `PRESENT_MODES`
+    #[cfg(not(target_arch = "wasm32"))]
+    CyclePresentMode,
+    /// UI scales are set in percent
+    SetUiScale(u8),
+    SetLanguage(Language),
+    SetTheme(Theme),
+    /// Waits for the next key press, to bind it to the `GameControl`
+    Rebind(GameControl),
+    /// Restores the default configuration, keeping the state of the current game
+    ResetConfig,
+    /// Copies the configuration to the clipboard as a string to share, see `share::share_config`
+    ExportConfig,
+    /// Applies a configuration shared by `ExportConfig` from the clipboard
+    ImportConfig,
+    /// Saves the game in the numbered slot
+    SaveGame(u8),
+    /// Loads the game from the numbered slot and continues it
+    LoadGame(u8),
+    /// A button of the game, forwarded as a `CustomMenuEvent` with its id
+    CustomAction(&'static str),
+}
+
+impl Actions {
+    /// Whether the action changes the persisted configuration
+    fn is_setter(&self) -> bool {
+        match self {
+            Self::SetFlag(_)
+            | Self::SetNum(_)
+            | Self::IncNum
+            | Self::DecNum
+            | Self::SetDifficulty(_)
+            | Self::SetSeed(_)
+            | Self::SetMusicVolume(_)
+            | Self::SetSfxVolume(_)
+            | Self::SetDisplayMode(_)
+            | Self::ToggleAutosave
+            | Self::ToggleMute
+            | Self::ToggleDuckMusic
+            | Self::ToggleColorblindMode
+            | Self::ToggleShareUsageData
+            | Self::ToggleRememberMenuPosition
+            | Self::SetUiScale(_)
+            | Self::SetLanguage(_)
+            | Self::SetTheme(_)
+            | Self::DismissWelcome
+            | Self::ResetConfig => true,
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::SetResolution(..) | Self::CyclePresentMode => true,
+            _ => false,
+        }
+    }
+    /// Whether the action is confirmed by the click sound. Pausing the game from the `Game` screen
+    /// and going `Back` only change the screen
+    pub fn clicks(&self) -> bool {
+        !matches!(self, Self::Pause | Self::Back)
+    }
+    /// Changes the `state` like `handle`, without sending the action
+    fn apply(&self, state: &mut GameCfg) {
+        match self {
+            Self::Pause | Self::Resume => (),
+            Self::Back => state.quit_requested = false,
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::Quit => (),
+            Self::SaveGame(_) => (),
+            Self::NewGame | Self::Restart | Self::LoadGame(_) => {
+                state.new_game = true;
+                state.outcome = None;
+            }
+            Self::QuitToMenu => {
+                state.new_game = false;
+                state.outcome = None;
+            }
+            Self::DismissWelcome => state.seen_welcome = true,
+            Self::SetFlag(name) => state.flags.toggle(name),
+            Self::SetNum(x) => {
+                state.num = (*x).clamp(state.num_min, state.num_max);
+                if state.num != *x {
+                    warn!(
+                        "Num {x} is outside of {}..={}, clamped to {}",
+                        state.num_min, state.num_max, state.num
+                    );
+                }
+            }
+            Self::IncNum => {
+                let (min, max) = (state.num_min, state.num_max);
+                state.num = state.num.saturating_add(1).clamp(min, max)
+            }
+            Self::DecNum => {
+                let (min, max) = (state.num_min, state.num_max);
+                state.num = state.num.saturating_sub(1).clamp(min, max)
+            }
+            Self::SetDifficulty(difficulty) => difficulty.apply(state),
+            Self::SetSeed(seed) => state.seed = *seed,
+            Self::SetMusicVolume(x) => state.music_volume = *x as f32 / 100.,
+            Self::SetSfxVolume(x) => state.sfx_volume = *x as f32 / 100.,
+            Self::SetDisplayMode(display_mode) => state.display_mode = *display_mode,
+            Self::ToggleAutosave => state.autosave ^= true,
+            Self::ToggleMute => state.muted ^= true,
+            Self::ToggleDuckMusic => state.duck_music ^= true,
+            Self::ToggleColorblindMode => state.colorblind_mode ^= true,
+            Self::ToggleShareUsageData => state.share_usage_data ^= true,
+            Self::ToggleRememberMenuPosition => state.remember_menu_position ^= true,
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::SetResolution(width, height) => state.resolution = (*width, *height),
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::CyclePresentMode => {
+                let next = PRESENT_MODES
+                    .iter()
+                    .position(|(mode, _)| *mode == state.present_mode)
+                    .map_or(0, |i| (i + 1) % PRESENT_MODES.len());
+                state.present_mode = PRESENT_MODES[next].0;
+            }
+            Self::SetUiScale(x) => state.ui_scale = *x as f32 / 100.,
+            Self::SetLanguage(language) => state.language = *language,
+            Self::SetTheme(theme) => state.theme = *theme,
+            Self::Rebind(control) => state.rebinding = Some(*control),
+            // `handle_events` resets to the `DefaultConfig` of the plugin then
+            Self::ResetConfig => *state = state.reset(&default()),
+            // The clipboard is only read in `share::share_config`
+            Self::ExportConfig | Self::ImportConfig => (),
+            Self::CustomAction(_) => (),
+        }
+    }
+}
+
+impl ActionTrait for Actions {
+    type State = GameCfg;
+    type Event = Self;
+    fn handle(&self, state: &mut Self::State, event_writer: &mut EventWriter<Self::Event>) {
+        self.apply(state);
+        // Setters are forwarded as well, so that `handle_events` can announce `ConfigChanged`
+        event_writer.send(*self)
+    }
+}
+impl ScreenTrait for Screens {
+    type Action = Actions;
+    type State = GameCfg;
+    fn resolve(
+        &self,
+        state: &<<Self as ScreenTrait>::Action as bevy_quickmenu::ActionTrait>::State,
+    ) -> bevy_quickmenu::Menu<Self> {
+        let tr = |key| state.tr(key);
+        let flags = Flags::NAMES.into_iter().zip(state.flag_labels);
+        let custom_screens = || {
+            state
+                .custom_screens
+                .iter()
+                .map(|screen| MenuItem::screen(tr(screen.id), Screens::Custom(screen.id)))
+        };
+        // Empty slots are labels, so that they can't be loaded
+        let slot_items = |action: fn(u8) -> Actions, slots: usize, empty_selectable: bool| {
+            (0..slots).map(move |i| {
+                let slot = if i == AUTOSAVE_SLOT.0 as usize {
+                    tr("Autosave").to_string()
+                } else {
+                    format!("{} {}", tr("Slot"), i + 1)
+                };
+                match state.saves[i] {
+                    Some(timestamp) => MenuItem::action(
+                        format!("{slot} - {}", save::format_timestamp(timestamp)),
+                        action(i as u8),
+                    ),
+                    None if empty_selectable => {
+                        MenuItem::action(format!("{slot} - {}", tr("Empty")), action(i as u8))
+                    }
+                    None => MenuItem::label(format!("{slot} - {}", tr("Empty"))),
+                }
+            })
+        };
+        Menu::new(
+            format!("{self:?}"),
+            match self {
+                Self::Pause => [
+                    MenuItem::headline(tr("Paused")),
+                    MenuItem::label(format!("{}: {}", tr("Seed"), state.game_seed)),
+                    MenuItem::action(with_key(tr("Resume"), KeyCode::R), Actions::Resume),
+                    MenuItem::action(tr("Restart"), Actions::Restart),
+                    MenuItem::screen(tr("Save Game"), Screens::SaveGame),
+                    MenuItem::screen(with_key(tr("Load Game"), KeyCode::L), Screens::LoadGame),
+                    // Asks first, as starting over discards the game in progress
+                    MenuItem::screen(
+                        with_key(tr("New Game"), KeyCode::N),
+                        Screens::ConfirmNewGame,
+                    ),
+                    MenuItem::screen(tr("Settings"), Screens::Settings),
+                    MenuItem::screen(tr("Help"), Screens::Help),
+                ]
+                .into_iter()
+                .chain(custom_screens())
+                .chain([
+                    MenuItem::action(
+                        with_key(tr("Quit to Main Menu"), KeyCode::M),
+                        Actions::QuitToMenu,
+                    ),
+                    #[cfg(not(target_arch = "wasm32"))]
+                    MenuItem::screen(with_key(tr("Quit"), KeyCode::Q), Screens::ConfirmQuit),
+                ])
+                .collect(),
+                Self::Game => vec![MenuItem::action(tr("Pause"), Actions::Pause)],
+                // The headline is colored as well, but the outcome is always spelled out
+                Self::GameOver => vec![
+                    MenuItem::headline(match state.outcome {
+                        Some(true) => tr("Victory"),
+                        Some(false) => tr("Defeat"),
+                        None => tr("Game Over"),
+                    }),
+                    MenuItem::label(match state.outcome {
+                        Some(true) => tr("You Win!"),
+                        Some(false) => tr("You Lose"),
+                        None => "",
+                    }),
+                    MenuItem::label(format!("{}: {}", tr("Seed"), state.game_seed)),
+                    MenuItem::action(with_key(tr("Retry"), KeyCode::R), Actions::Restart),
+                    MenuItem::action(with_key(tr("New Game"), KeyCode::N), Actions::NewGame),
+                    #[cfg(not(target_arch = "wasm32"))]
+                    MenuItem::screen(with_key(tr("Quit"), KeyCode::Q), Screens::ConfirmQuit),
+                ],
+                Self::NewGame => [
+                    MenuItem::headline(state.title),
+                    MenuItem::action(
+                        with_key(tr("Start a New Game"), KeyCode::N),
+                        Actions::NewGame,
+                    ),
+                    MenuItem::screen(with_key(tr("Load Game"), KeyCode::L), Screens::LoadGame),
+                    MenuItem::screen(tr("Settings"), Screens::Settings),
+                    MenuItem::screen(tr("Help"), Screens::Help),
+                    MenuItem::screen(tr("Credits"), Screens::Credits),
+                ]
+                .into_iter()
+                .chain(custom_screens())
+                .chain(
+                    state
+                        .show_version
+                        .then(|| MenuItem::label(format!("v{}", env!("CARGO_PKG_VERSION")))),
+                )
+                .collect(),
+                Self::Settings => [MenuItem::headline(tr("Settings"))]
+                    .into_iter()
+                    .chain(flags.map(|(name, label)| {
+                        MenuItem::action(tr(label), Actions::SetFlag(name))
+                            .checked(state.flags.get(name))
+                    }))
+                    .chain([
+                        MenuItem::screen(tr("Difficulty"), Screens::Difficulty),
+                        MenuItem::screen(tr(tooltip::SEED.label), Screens::Seed),
+                        MenuItem::screen(tr("Num"), Screens::Num),
+                        MenuItem::screen(tr("Audio"), Screens::Audio),
+                        MenuItem::action(tr("Mute"), Actions::ToggleMute).checked(state.muted),
+                        MenuItem::screen(tr("Controls"), Screens::Controls),
+                        MenuItem::screen(tr("Window Mode"), Screens::DisplayMode),
+                        MenuItem::action(tr(tooltip::AUTOSAVE.label), Actions::ToggleAutosave)
+                            .checked(state.autosave),
+                        #[cfg(not(target_arch = "wasm32"))]
+                        MenuItem::screen(tr("Resolution"), Screens::Resolution),
+                        #[cfg(not(target_arch = "wasm32"))]
+                        MenuItem::action(
+                            format!(
+                                "{}: {}",
+                                tr("VSync"),
+                                tr(present_mode_name(state.present_mode))
+                            ),
+                            Actions::CyclePresentMode,
+                        ),
+                        MenuItem::screen(tr("UI Scale"), Screens::UiScale),
+                        MenuItem::screen(tr("Language"), Screens::Language),
+                        MenuItem::screen(tr("Theme"), Screens::Theme),
+                        MenuItem::action(
+                            tr(tooltip::COLORBLIND_MODE.label),
+                            Actions::ToggleColorblindMode,
+                        )
+                        .checked(state.colorblind_mode),
+                        MenuItem::action(
+                            tr(tooltip::SHARE_USAGE_DATA.label),
+                            Actions::ToggleShareUsageData,
+                        )
+                        .checked(state.share_usage_data),
+                        MenuItem::action(
+                            tr("Remember Menu Position"),
+                            Actions::ToggleRememberMenuPosition,
+                        )
+                        .checked(state.remember_menu_position),
+                        MenuItem::action(tr("Reset to Defaults"), Actions::ResetConfig),
+                        MenuItem::action(tr("Export Config"), Actions::ExportConfig),
+                        MenuItem::action(tr("Import Config"), Actions::ImportConfig),
+                    ])
+                    .chain(state.clipboard_error.map(tr).map(MenuItem::label))
+                    .chain([MenuItem::action(tr("Back"), Actions::Back)])
+                    .collect(),
+                Self::Difficulty => [MenuItem::headline(tr("Difficulty"))]
+                    .into_iter()
+                    .chain(Difficulty::ALL.map(|difficulty| {
+                        MenuItem::action(tr(difficulty.name()), Actions::SetDifficulty(difficulty))
+                            .checked(Difficulty::of(state) == Some(difficulty))
+                    }))
+                    .chain(
+                        Difficulty::of(state)
+                            .is_none()
+                            .then(|| MenuItem::label(tr("Custom"))),
+                    )
+                    .chain([MenuItem::action(tr("Back"), Actions::Back)])
+                    .collect(),
+                // Without text entry, the seed of the last game can be picked to replay it
+                Self::Seed => {
+                    let mut items = vec![
+                        MenuItem::headline(tr("Seed")),
+                        MenuItem::action(tr("Random"), Actions::SetSeed(0))
+                            .checked(state.seed == 0),
+                    ];
+                    let replay = [state.seed, state.game_seed]
+                        .into_iter()
+                        .find(|&seed| seed != 0);
+                    if let Some(seed) = replay {
+                        let label = format!("{} {seed}", tr("Replay"));
+                        items.push(
+                            MenuItem::action(label, Actions::SetSeed(seed))
+                                .checked(state.seed == seed),
+                        );
+                    }
+                    items.push(MenuItem::action(tr("Back"), Actions::Back));
+                    items
+                }
+                Self::Num => [
+                    MenuItem::headline(tr("Num")),
+                    MenuItem::action("+", Actions::IncNum),
+                    MenuItem::action("-", Actions::DecNum),
+                ]
+                .into_iter()
+                .chain(slider::slider(state, &slider::NUM))
+                .chain([MenuItem::action(tr("Back"), Actions::Back)])
+                .collect(),
+                Self::Audio => [MenuItem::headline(tr("Audio"))]
+                    .into_iter()
+                    .chain(slider::slider(state, &slider::MUSIC_VOLUME))
+                    .chain(slider::slider(state, &slider::SFX_VOLUME))
+                    .chain([
+                        MenuItem::action(tr("Duck Music"), Actions::ToggleDuckMusic)
+                            .checked(state.duck_music),
+                        MenuItem::action(tr("Back"), Actions::Back),
+                    ])
+                    .collect(),
+                Self::Controls => [MenuItem::headline(tr("Controls"))]
+                    .into_iter()
+                    .chain(GameControl::ALL.map(|control| {
+                        let key = if state.rebinding == Some(control) {
+                            tr("press a key").to_string()
+                        } else {
+                            format!("{:?}", state.keys.key(control))
+                        };
+                        MenuItem::action(
+                            format!("{}: {key}", tr(control.name())),
+                            Actions::Rebind(control),
+                        )
+                    }))
+                    .chain([MenuItem::action(tr("Back"), Actions::Back)])
+                    .collect(),
+                // Built from the same `KeyBindings` as `Controls`, so it shows any rebinding
+                Self::Help => [MenuItem::headline(tr("Help"))]
+                    .into_iter()
+                    .chain(GameControl::ALL.map(|control| {
+                        MenuItem::label(format!(
+                            "{}: {:?}",
+                            tr(control.name()),
+                            state.keys.key(control)
+                        ))
+                    }))
+                    .chain([
+                        MenuItem::label(tr("Arrow keys also move")),
+                        MenuItem::label(format!(
+                            "{}: {:?}/{:?}, {}",
+                            tr("Menu"),
+                            state.keys.up,
+                            state.keys.down,
+                            tr("Space or Enter to select")
+                        )),
+                        MenuItem::action(tr("Back"), Actions::Back),
+                    ])
+                    .collect(),
+                Self::UiScale => [MenuItem::headline(tr("UI Scale"))]
+                    .into_iter()
+                    .chain(UI_SCALES.map(|x| {
+                        MenuItem::action(format!("{x}%"), Actions::SetUiScale(x))
+                            .checked((state.ui_scale * 100.).round() as u8 == x)
+                    }))
+                    .chain([MenuItem::action(tr("Back"), Actions::Back)])
+                    .collect(),
+                Self::Language => [MenuItem::headline(tr("Language"))]
+                    .into_iter()
+                    .chain(Language::ALL.map(|language| {
+                        MenuItem::action(
+                            language.localization().name,
+                            Actions::SetLanguage(language),
+                        )
+                        .checked(state.language == language)
+                    }))
+                    .chain([MenuItem::action(tr("Back"), Actions::Back)])
+                    .collect(),
+                Self::Theme => [MenuItem::headline(tr("Theme"))]
+                    .into_iter()
+                    .chain(Theme::ALL.map(|theme| {
+                        MenuItem::action(tr(theme.name()), Actions::SetTheme(theme))
+                            .checked(state.theme == theme)
+                    }))
+                    .chain([MenuItem::action(tr("Back"), Actions::Back)])
+                    .collect(),
+                Self::DisplayMode => [MenuItem::headline(tr("Window Mode"))]
+                    .into_iter()
+                    .chain(DisplayMode::ALL.map(|mode| {
+                        MenuItem::action(tr(mode.name()), Actions::SetDisplayMode(mode))
+                            .checked(state.display_mode == mode)
+                    }))
+                    .chain([MenuItem::action(tr("Back"), Actions::Back)])
+                    .collect(),
+                Self::Credits => [MenuItem::headline(tr("Credits"))]
+                    .into_iter()
+                    .chain(state.credits.iter().map(|&line| MenuItem::label(line)))
+                    .chain([MenuItem::action(tr("Back"), Actions::Back)])
+                    .collect(),
+                Self::SaveGame => [MenuItem::headline(tr("Save Game"))]
+                    .into_iter()
+                    .chain(slot_items(Actions::SaveGame, SAVE_SLOTS, true))
+                    .chain([MenuItem::action(tr("Back"), Actions::Back)])
+                    .collect(),
+                Self::LoadGame => [MenuItem::headline(tr("Load Game"))]
+                    .into_iter()
+                    .chain(slot_items(Actions::LoadGame, state.saves.len(), false))
+                    .chain([MenuItem::action(tr("Back"), Actions::Back)])
+                    .collect(),
+                #[cfg(not(target_arch = "wasm32"))]
+                Self::Resolution => [MenuItem::headline(tr("Resolution"))]
+                    .into_iter()
+                    .chain(RESOLUTIONS.map(|(width, height)| {
+                        MenuItem::action(
+                            format!("{width}x{height}"),
+                            Actions::SetResolution(width, height),
+                        )
+                        .checked(state.resolution == (width, height))
+                    }))
+                    .chain([MenuItem::action(tr("Back"), Actions::Back)])
+                    .collect(),
+                Self::Welcome => vec![
+                    MenuItem::headline(format!("{} {}", tr("Welcome to"), state.title)),
+                    MenuItem::label(tr("Fly with WASD or the arrow keys")),
+                    MenuItem::label(tr("Escape pauses the game")),
+                    MenuItem::action(tr("Got it"), Actions::DismissWelcome),
+                ],
+                Self::ConfirmNewGame => vec![
+                    MenuItem::headline(tr("Discard the current game?")),
+                    MenuItem::action(
+                        with_key(tr("Yes, Start Over"), KeyCode::Y),
+                        Actions::NewGame,
+                    ),
+                    MenuItem::action(with_key(tr("No, Cancel"), KeyCode::N), Actions::Back),
+                ],
+                Self::Custom(id) => {
+                    match state.custom_screens.iter().find(|screen| screen.id == *id) {
+                        Some(screen) => (screen.build)(state),
+                        None => vec![
+                            MenuItem::label(format!("Unknown screen {id}")),
+                            MenuItem::action(tr("Back"), Actions::Back),
+                        ],
+                    }
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                Self::ConfirmQuit => vec![
+                    MenuItem::headline(tr("Quit?")),
+                    MenuItem::action(with_key(tr("Yes, Quit"), KeyCode::Y), Actions::Quit),
+                    MenuItem::action(with_key(tr("No, Cancel"), KeyCode::N), Actions::Back),
+                ],
+            },
+        )
+    }
+}
+
+/// UI scales, in percent, offered by the `UiScale` screen
+const UI_SCALES: [u8; 4] = [75, 100, 125, 150];
+
+/// Window sizes offered by the `Resolution` screen
+#[cfg(not(target_arch = "wasm32"))]
+const RESOLUTIONS: [(u16, u16); 4] = [(800, 600), (1280, 720), (1600, 900), (1920, 1080)];
+
+/// Present modes offered by the VSync entry of the `Settings` screen, with their names. Browsers
+/// decide it themselves on wasm
+#[cfg(not(target_arch = "wasm32"))]
+const PRESENT_MODES: [(PresentMode, &str); 3] = [
+    (PresentMode::Fifo, "On"),
+    (PresentMode::Immediate, "Off"),
+    (PresentMode::Mailbox, "Fast"),
+];
+
+#[cfg(not(target_arch = "wasm32"))]
+fn present_mode_name(present_mode: PresentMode) -> &'static str {
+    PRESENT_MODES
+        .iter()
+        .find(|(mode, _)| *mode == present_mode)
+        .map_or("On", |(_, name)| name)
+}
+
+/// On/off options of `YourGame`, each shown as a checked item on the `Settings` screen
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Flags {
+    pub boolean: bool,
+    pub screen_shake: bool,
+    pub tutorials: bool,
+    pub show_fps: bool,
+    /// Rumbles the gamepad on confirming and leaving menu screens
+    pub rumble: bool,
+}
+impl Default for Flags {
+    fn default() -> Self {
+        Self {
+            boolean: true,
+            screen_shake: true,
+            tutorials: true,
+            show_fps: false,
+            rumble: false,
+        }
+    }
+}
+impl Flags {
+    /// Names of the flags, used by `Actions::SetFlag` and as their labels
+    pub const NAMES: [&'static str; 5] =
+        ["Boolean", "Screen Shake", "Tutorials", "Show FPS", "Rumble"];
+
+    fn flag_mut(&mut self, name: &str) -> Option<&mut bool> {
+        match name {
+            "Boolean" => Some(&mut self.boolean),
+            "Screen Shake" => Some(&mut self.screen_shake),
+            "Tutorials" => Some(&mut self.tutorials),
+            "Show FPS" => Some(&mut self.show_fps),
+            "Rumble" => Some(&mut self.rumble),
+            _ => None,
+        }
+    }
+    /// Value of the flag, `false` for unknown names
+    pub fn get(&self, name: &str) -> bool {
+        match name {
+            "Boolean" => self.boolean,
+            "Screen Shake" => self.screen_shake,
+            "Tutorials" => self.tutorials,
+            "Show FPS" => self.show_fps,
+            "Rumble" => self.rumble,
+            _ => false,
+        }
+    }
+    pub fn toggle(&mut self, name: &str) {
+        match self.flag_mut(name) {
+            Some(flag) => *flag ^= true,
+            None => warn!("Unknown flag {name}"),
+        }
+    }
+}
+
+/// Labels of the `Flags` on the `Settings` screen, in the order of `Flags::NAMES`. Insert it to
+/// give the flags of your game meaningful names, e.g. "Sound Effects" instead of "Boolean". The
+/// names stay the same for `Actions::SetFlag`
+#[derive(Resource, Clone, Copy)]
+pub struct FlagLabels(pub [&'static str; Flags::NAMES.len()]);
+
+impl Default for FlagLabels {
+    fn default() -> Self {
+        FlagLabels(Flags::NAMES)
+    }
+}
+
+/// Resource to hold the Configurations for `YourGame`
+/// Only the configuration fields are persisted, the per game fields are skipped
+#[derive(Resource, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameCfg {
+    /// Version of the config format, see `config::migrate`
+    #[serde(default = "config::unversioned")]
+    pub version: u32,
+    /// Name of the game, used for the window title and the `NewGame` headline
+    #[serde(skip)]
+    pub title: &'static str,
+    /// Lines shown on the `Credits` screen
+    #[serde(skip)]
+    pub credits: &'static [&'static str],
+    /// Shows the version of the game on the `NewGame` screen, for bug reports. Turn it off for
+    /// release builds if it is in the way
+    #[serde(skip)]
+    pub show_version: bool,
+    /// Placement of the menu screens
+    #[serde(skip)]
+    pub menu_anchor: MenuAnchor,
+    /// Placement of the `Game` overlay, e.g. of a pause button
+    #[serde(skip)]
+    pub game_anchor: MenuAnchor,
+    /// Root of the menu until a game is started, see `MenuPlugin::initial_screen`
+    #[serde(skip)]
+    pub initial_screen: Screens,
+    pub flags: Flags,
+    /// Whether a game was started, or loaded, this session. Until then the menu opens at
+    /// `NewGame`, afterwards at `Pause`, see `root_screen`
+    #[serde(skip)]
+    pub new_game: bool,
+    #[serde(skip)]
+    pub outcome: Option<bool>,
+    /// The window was asked to close during a game, and the menu opens at `ConfirmQuit`
+    #[serde(skip)]
+    pub quit_requested: bool,
+    /// Enables the dev hotkeys like `KeyBindings::new_game`, by default only in debug builds
+    #[serde(skip)]
+    pub dev_hotkeys: bool,
+    pub num: u8,
+    /// Range of `num` offered by the `Num` screen
+    #[serde(skip)]
+    pub num_min: u8,
+    #[serde(skip)]
+    pub num_max: u8,
+    /// Seed of the `GameRng` of new games, 0 picks a random one
+    pub seed: u64,
+    /// Seed of the current or last game
+    #[serde(skip)]
+    pub game_seed: u64,
+    /// Multiplier of the player speed, set along with `num` by the `Difficulty` presets
+    pub speed: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    /// Whether the `Welcome` screen was dismissed. Only a missing config file opens it, so that
+    /// configs written before it was added don't show it
+    pub seen_welcome: bool,
+    /// Silences all audio, regardless of the volumes
+    pub muted: bool,
+    /// Lowers the music for a moment while a menu sound plays, see `MusicDucking`
+    pub duck_music: bool,
+    pub display_mode: DisplayMode,
+    /// Replaced by `display_mode` in version 3, only read by `config::migrate`
+    #[serde(skip_serializing)]
+    fullscreen: bool,
+    /// Window size, the browser decides it on wasm
+    pub resolution: (u16, u16),
+    /// Last position of the window on desktop, `None` keeps it centered
+    pub window_position: Option<(i32, i32)>,
+    /// Opacity of the background of the pause menu, lower values let the game show through
+    pub pause_dim_alpha: f32,
+    /// Scale of the UI, including the menu text
+    pub ui_scale: f32,
+    /// VSync of the window on desktop
+    pub present_mode: PresentMode,
+    pub language: Language,
+    pub theme: Theme,
+    /// Highlights the focused item by contrast instead of by color
+    pub colorblind_mode: bool,
+    /// Opts in to report the menu actions to the `MenuObservers`
+    pub share_usage_data: bool,
+    /// Reopens the pause menu at the screen it was left on, instead of at its root
+    pub remember_menu_position: bool,
+    pub keys: KeyBindings,
+    /// The `GameControl` waiting for a key on the `Controls` screen
+    #[serde(skip)]
+    pub rebinding: Option<GameControl>,
+    /// Saves to the `AUTOSAVE_SLOT` whenever the game is paused
+    pub autosave: bool,
+    /// Seconds without input after which the `GameOver` screen returns to `NewGame`, e.g. for
+    /// kiosks. `None` stays on it
+    pub gameover_timeout: Option<f32>,
+    /// Timestamps of the save slots and the autosave, listed on the `SaveGame` and `LoadGame`
+    /// screens
+    #[serde(skip)]
+    pub saves: [Option<u64>; SAVE_SLOTS + 1],
+    /// The screens registered with `RegisterScreen`, set when the config is loaded
+    #[serde(skip)]
+    pub custom_screens: &'static [CustomScreen],
+    /// The `FlagLabels`, set when the config is loaded
+    #[serde(skip)]
+    pub flag_labels: [&'static str; Flags::NAMES.len()],
+    /// Why the last `ExportConfig` or `ImportConfig` failed, shown on the `Settings` screen
+    #[serde(skip)]
+    pub clipboard_error: Option<&'static str>,
+}
+impl Default for GameCfg {
+    fn default() -> Self {
+        Self {
+            version: config::CONFIG_VERSION,
+            title: "YourGame",
+            credits: &["Made with Bevy", "Bevy icon: MIT License"],
+            show_version: true,
+            menu_anchor: MenuAnchor::Flow,
+            game_anchor: MenuAnchor::TopLeft,
+            initial_screen: Screens::NewGame,
+            flags: Flags::default(),
+            new_game: false,
+            dev_hotkeys: cfg!(debug_assertions),
+            quit_requested: false,
+            outcome: None,
+            num: 3,
+            num_min: 3,
+            num_max: 5,
+            seed: 0,
+            game_seed: 0,
+            speed: 1.,
+            music_volume: 1.,
+            sfx_volume: 1.,
+            seen_welcome: true,
+            muted: false,
+            duck_music: true,
+            display_mode: DisplayMode::Windowed,
+            fullscreen: false,
+            resolution: (800, 600),
+            window_position: None,
+            pause_dim_alpha: 1.,
+            ui_scale: 1.,
+            present_mode: PresentMode::Fifo,
+            language: Language::English,
+            theme: Theme::Dark,
+            colorblind_mode: false,
+            share_usage_data: false,
+            remember_menu_position: false,
+            keys: KeyBindings::default(),
+            rebinding: None,
+            autosave: false,
+            gameover_timeout: None,
+            saves: [None; SAVE_SLOTS + 1],
+            custom_screens: &[],
+            flag_labels: Flags::NAMES,
+            clipboard_error: None,
+        }
+    }
+}
+
+impl GameCfg {
+    /// Copies the fields the menu edits with `Actions::handle`. The others are set outside of the
+    /// menu, like `outcome` or `saves`, and shown in it by `refresh_menu_state`
+    fn update_from_menu(&mut self, menu: &GameCfg) {
+        *self = GameCfg {
+            flags: menu.flags,
+            new_game: menu.new_game,
+            num: menu.num,
+            seed: menu.seed,
+            speed: menu.speed,
+            music_volume: menu.music_volume,
+            sfx_volume: menu.sfx_volume,
+            seen_welcome: menu.seen_welcome,
+            muted: menu.muted,
+            duck_music: menu.duck_music,
+            display_mode: menu.display_mode,
+            ui_scale: menu.ui_scale,
+            present_mode: menu.present_mode,
+            language: menu.language,
+            theme: menu.theme,
+            colorblind_mode: menu.colorblind_mode,
+            share_usage_data: menu.share_usage_data,
+            remember_menu_position: menu.remember_menu_position,
+            keys: menu.keys,
+            rebinding: menu.rebinding,
+            autosave: menu.autosave,
+            ..*self
+        };
+    }
+    /// The `Difficulty` to play at, for game code instead of reading `num`. That is the matching
+    /// preset, or the one `num` falls into for custom values
+    pub fn difficulty(&self) -> Difficulty {
+        Difficulty::of(self).unwrap_or_else(|| Difficulty::from_num(self))
+    }
+    /// The `defaults`, keeping the state of the current game
+    fn reset(&self, defaults: &GameCfg) -> GameCfg {
+        GameCfg {
+            new_game: self.new_game,
+            outcome: self.outcome,
+            game_seed: self.game_seed,
+            saves: self.saves,
+            custom_screens: self.custom_screens,
+            flag_labels: self.flag_labels,
+            ..*defaults
+        }
+    }
+    /// The fields set by the game rather than the player, which aren't persisted, from `defaults`
+    fn with_game_fields(self, defaults: &GameCfg) -> GameCfg {
+        GameCfg {
+            title: defaults.title,
+            credits: defaults.credits,
+            show_version: defaults.show_version,
+            menu_anchor: defaults.menu_anchor,
+            game_anchor: defaults.game_anchor,
+            initial_screen: defaults.initial_screen,
+            dev_hotkeys: defaults.dev_hotkeys,
+            num_min: defaults.num_min,
+            num_max: defaults.num_max,
+            ..self
+        }
+    }
+    /// Brings values of a hand-edited config back into their ranges, called by `load_config`
+    pub fn sanitize(&mut self) {
+        self.music_volume = self.music_volume.clamp(0., 1.);
+        self.sfx_volume = self.sfx_volume.clamp(0., 1.);
+        self.num = self.num.clamp(self.num_min, self.num_max);
+        self.gameover_timeout = self.gameover_timeout.filter(|timeout| *timeout > 0.);
+        // A stale outcome would open the `GameOver` screen on launch
+        self.outcome = None;
+    }
+    #[deprecated(note = "use `flags.boolean`")]
+    pub fn boolean(&self) -> bool {
+        self.flags.boolean
+    }
+    /// Translates a menu string to the configured `language`
+    pub fn tr(&self, key: &'static str) -> &'static str {
+        self.language.localization().tr(key)
+    }
+}
+
+/// Sent by `handle_events` whenever a setter action changes the `GameCfg`. Prefer reading this
+/// event over polling `Res<GameCfg>` for changes
+#[derive(Clone, Copy)]
+pub struct ConfigChanged {
+    pub previous: GameCfg,
+    pub current: GameCfg,
+}
+
+/// Marker for the camera rendering the quickmenu. Games adding their own 2d camera can query
+/// for it, to reuse or replace the menu camera
+#[derive(Component)]
+pub struct MenuCamera;
+
+/// Spawns the `MenuCamera`, unless one is already present
+fn setup_menu_camera(mut commands: Commands, camera: Query<(), With<MenuCamera>>) {
+    if camera.is_empty() {
+        commands.spawn((Camera2dBundle::default(), MenuCamera));
+    }
+}
+
+/// The `Stylesheet` of the menu placed at the `anchor`, the one given to `MenuPlugin::stylesheet`
+/// or else the one built from the `MenuTheme`
+fn stylesheet(
+    cfg: &GameCfg,
+    anchor: MenuAnchor,
+    theme: &MenuTheme,
+    assets: &MenuAssets,
+) -> Stylesheet {
+    // Relative to the window, so that the menu still fits at a large `ui_scale`
+    let mut style = Style {
+        max_size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+        overflow: Overflow::Hidden,
+        ..default()
+    };
+    anchor.apply(&mut style);
+    let font = assets.font.as_deref();
+    match assets.stylesheet.as_deref() {
+        Some(MenuStylesheet(sheet)) => sheet.clone(),
+        None => themed_stylesheet(theme, cfg.colorblind_mode, font, &assets.fonts),
+    }
+    .with_style(style)
+}
+
+/// The `Stylesheet` built from the `MenuTheme`. It uses the `FontAssets::fira_sans` once it is
+/// loaded and the default font until then
+fn themed_stylesheet(
+    theme: &MenuTheme,
+    colorblind_mode: bool,
+    font: Option<&FontAssets>,
+    fonts: &Assets<Font>,
+) -> Stylesheet {
+    let mut sheet = Stylesheet::default().with_background(BackgroundColor(theme.background));
+    sheet.headline.fg = theme.headline;
+    sheet.label.fg = theme.text;
+    sheet.button.normal.fg = theme.text;
+    // Inverting text and background stands out regardless of the hues of the theme
+    let (highlight_fg, highlight_bg) = if colorblind_mode {
+        (theme.background, theme.text)
+    } else {
+        (theme.text, theme.highlight)
+    };
+    for entry in [&mut sheet.button.hover, &mut sheet.button.selected] {
+        entry.fg = highlight_fg;
+        entry.bg = highlight_bg;
+    }
+    if let Some(font) = font
+        .map(|font| &font.fira_sans)
+        .filter(|font| fonts.contains(*font))
+    {
+        for entry in [
+            &mut sheet.headline,
+            &mut sheet.label,
+            &mut sheet.button.normal,
+            &mut sheet.button.hover,
+            &mut sheet.button.selected,
+        ] {
+            entry.font = font.clone();
+        }
+    }
+    sheet
+}
+
+/// The assets the menu is styled with, besides the `MenuTheme`
+#[derive(SystemParam)]
+struct MenuAssets<'w> {
+    font: Option<Res<'w, FontAssets>>,
+    fonts: Res<'w, Assets<Font>>,
+    backgrounds: Option<Res<'w, MenuBackgrounds>>,
+    stylesheet: Option<Res<'w, MenuStylesheet>>,
+}
+
+/// The `Stylesheet` given to `MenuPlugin::stylesheet`, replacing the one built from the
+/// `MenuTheme`
+#[derive(Resource)]
+struct MenuStylesheet(Stylesheet);
+
+/// The config used without a stored one, and by `Actions::ResetConfig`, see
+/// `MenuPlugin::default_config`
+#[derive(Resource)]
+pub struct DefaultConfig(pub GameCfg);
+
+/// Send this from the game logic to end the game, `true` if the player won. The menu then shows
+/// the `GameOver` screen
+pub struct GameResultEvent(pub bool);
+
+/// Sent by `Actions::Restart`, right before switching back to `GameState::Game`. Game plugins
+/// tear down their entities on it, so that they are rebuilt like for a new game. The `GameRng` is
+/// already re-seeded with the `GameCfg::game_seed` and the config is left untouched
+pub struct RestartGameEvent;
+
+/// Sent by `Actions::QuitToMenu`, when the current game is abandoned for the `NewGame` screen.
+/// Game plugins tear down their entities on it, the next game is started like the first one
+pub struct QuitGameEvent;
+
+/// Sent by `Actions::NewGame`, right before switching to `GameState::Game`, with the config the new
+/// game is played with. Game setup should read it from here rather than from `Res<GameCfg>`, which
+/// may still be written while the menu fades out
+pub struct StartGameEvent {
+    pub config: GameCfg,
+}
+
+fn handle_game_result(
+    mut result_event: EventReader<GameResultEvent>,
+    mut fade_event: EventWriter<FadeTransition>,
+    mut cfg: ResMut<GameCfg>,
+) {
+    if let Some(GameResultEvent(won)) = result_event.iter().last() {
+        cfg.outcome = Some(*won);
+        fade_event.send(FadeTransition(GameState::Menu));
+    }
+}
+
+/// Opens the `NewGame` screen once loading is done, so that the menu doesn't show over the splash
+fn setup_menu(
+    mut commands: Commands,
+    cfg: Res<GameCfg>,
+    state: Res<State<GameState>>,
+    assets: MenuAssets,
+) {
+    let theme = cfg.theme.menu_theme();
+    commands.insert_resource(root_menu(&state.0, &cfg, &theme, &assets));
+    commands.insert_resource(theme);
+}
+
+/// Despawns the quickmenu UI when leaving `GameState::Menu`, so that none of it lingers into the
+/// game. The `Game` screen is then built anew by `menu`
+fn cleanup_menu(
+    mut commands: Commands,
+    menu_ui: Query<Entity, (With<QuickMenuComponent>, Without<Parent>)>,
+) {
+    for entity in &menu_ui {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// The screen the menu starts from in `state`
+fn root_screen(state: &GameState, cfg: &GameCfg) -> Screens {
+    match state {
+        GameState::Game => Screens::Game,
+        _ if cfg.outcome.is_some() => Screens::GameOver,
+        #[cfg(not(target_arch = "wasm32"))]
+        _ if cfg.quit_requested => Screens::ConfirmQuit,
+        _ if cfg.new_game => Screens::Pause,
+        _ if !cfg.seen_welcome => Screens::Welcome,
+        _ => cfg.initial_screen,
+    }
+}
+
+/// Run condition for gameplay systems that should ignore input while a menu is open, e.g.
+/// `.add_system(shoot.run_if(not(menu_is_open)))`. Every screen but the `Game` overlay is shown in
+/// `GameState::Menu`, including the sub-screens and the `GameOver` screen, see `game_is_over`
+pub fn menu_is_open(state: Res<State<GameState>>) -> bool {
+    state.0 == GameState::Menu
+}
+
+/// Run condition for end-game effects, e.g. `.add_system(confetti.run_if(game_is_over))`. The
+/// `GameOver` screen is shown in `GameState::Menu` like the pause menu, this tells them apart. It
+/// already holds while the game fades into the `GameOver` screen
+pub fn game_is_over(cfg: Res<GameCfg>) -> bool {
+    cfg.outcome.is_some()
+}
+
+/// The menu at the `root_screen`, the `Game` screen is an overlay over the game
+fn root_menu(
+    state: &GameState,
+    cfg: &GameCfg,
+    theme: &MenuTheme,
+    assets: &MenuAssets,
+) -> MenuState<Screens> {
+    let screen = root_screen(state, cfg);
+    let anchor = if screen == Screens::Game {
+        cfg.game_anchor
+    } else {
+        cfg.menu_anchor
+    };
+    let mut sheet = stylesheet(cfg, anchor, theme, assets);
+    let has_image = assets
+        .backgrounds
+        .as_ref()
+        .map_or(false, |backgrounds| backgrounds.0.contains_key(&screen));
+    if has_image {
+        sheet = sheet.with_background(BackgroundColor(Color::NONE));
+    } else if screen == Screens::Pause {
+        // The game stays in the world while paused, and shows through a translucent background
+        let background = theme.background.with_a(cfg.pause_dim_alpha.clamp(0., 1.));
+        sheet = sheet.with_background(BackgroundColor(background));
+    } else if let Some(won) = cfg.outcome.filter(|_| screen == Screens::GameOver) {
+        // The other root screens get a fresh stylesheet, so leaving `GameOver` reverts it
+        let background = if won { theme.victory } else { theme.defeat };
+        sheet = sheet.with_background(BackgroundColor(background));
+    }
+    if let Some(won) = cfg.outcome {
+        sheet.headline.fg = if won { Color::GREEN } else { Color::RED };
+    }
+    MenuState::new(*cfg, screen, Some(sheet))
+}
+
+/// The pause menu left for the game while `GameCfg::remember_menu_position` is set, with the
+/// screens open in it
+#[derive(Resource, Default)]
+struct SavedMenu {
+    menu: Option<MenuState<Screens>>,
+    /// Set by `Actions::Resume` from the pause menu. New games start over at its root
+    resumed: bool,
+}
+
+/// Sets `Screens` for the quickmenu, window title
+fn menu(
+    mut commands: Commands,
+    mut window: Query<&mut Window, With<PrimaryWindow>>,
+    cfg: Res<GameCfg>,
+    state: Res<State<GameState>>,
+    theme: Res<MenuTheme>,
+    assets: MenuAssets,
+    menu_state: Option<ResMut<MenuState<Screens>>>,
+    mut saved: ResMut<SavedMenu>,
+) {
+    let screen = root_screen(&state.0, &cfg);
+    // Headless builds have no window, but still get the menu state
+    match window.get_single_mut() {
+        Ok(mut window) => {
+            window.title = match screen {
+                Screens::Game => cfg.title.to_string(),
+                Screens::GameOver => format!("{} - {}", cfg.title, cfg.tr("Game Over")),
+                _ => format!("{} - {}", cfg.title, cfg.tr("Paused")),
+            }
+        }
+        Err(_) => debug!("No primary window to set the title of"),
+    }
+    let mut menu = root_menu(&state.0, &cfg, &theme, &assets);
+    // Only the pause menu continues where it was left, the other roots start over
+    if screen != Screens::Game {
+        let restores = screen == Screens::Pause && cfg.remember_menu_position;
+        if let Some(left) = saved.menu.take().filter(|_| restores) {
+            menu = left;
+            *menu.state_mut() = *cfg;
+        }
+    }
+    match menu_state {
+        Some(mut menu_state) => {
+            let left = std::mem::replace(&mut *menu_state, menu);
+            if screen == Screens::Game && saved.resumed && cfg.remember_menu_position {
+                saved.menu = Some(left);
+            }
+        }
+        None => commands.insert_resource(menu),
+    }
+    saved.resumed = false;
+}
+
+/// Applies a `Theme` chosen on the `Theme` screen, or the `colorblind_mode`. The stylesheet is only
+/// read when the menu is built, so the menu is rebuilt at its root screen. That is also how the
+/// `Welcome` screen is left for `NewGame`, a canceled `ConfirmQuit` for `Pause`, and the `Pause`
+/// screen for `NewGame` by `Actions::QuitToMenu`
+fn apply_theme(
+    mut commands: Commands,
+    mut config_event: EventReader<ConfigChanged>,
+    state: Res<State<GameState>>,
+    assets: MenuAssets,
+) {
+    let Some(ConfigChanged { previous, current }) = config_event.iter().last() else { return };
+    let rebuilds = |cfg: &GameCfg| {
+        (
+            cfg.theme,
+            cfg.colorblind_mode,
+            cfg.seen_welcome,
+            cfg.quit_requested,
+            cfg.new_game,
+        )
+    };
+    if rebuilds(previous) == rebuilds(current) {
+        return;
+    }
+    let theme = current.theme.menu_theme();
+    commands.insert_resource(root_menu(&state.0, current, &theme, &assets));
+    commands.insert_resource(theme);
+}
+
+/// Shows the changes of the `GameCfg` made outside of the menu, like new `saves`, in the open
+/// screens. The fields the menu edits are kept, as `handle_events` may not have copied them yet
+fn refresh_menu_state(cfg: Res<GameCfg>, menu_state: Option<ResMut<MenuState<Screens>>>) {
+    let Some(mut menu_state) = menu_state.filter(|_| cfg.is_changed()) else { return };
+    let state = menu_state.state_mut();
+    let edited = *state;
+    *state = *cfg;
+    state.update_from_menu(&edited);
+}
+
+fn handle_events(
+    mut commands: Commands,
+    mut action_event: EventReader<Actions>,
+    mut config_event: EventWriter<ConfigChanged>,
+    mut fade_event: EventWriter<FadeTransition>,
+    mut navigation_event: EventWriter<NavigationEvent>,
+    mut save_event: EventWriter<SaveGameEvent>,
+    mut load_event: EventWriter<LoadGameEvent>,
+    mut restart_event: EventWriter<RestartGameEvent>,
+    mut start_event: EventWriter<StartGameEvent>,
+    mut quit_event: EventWriter<QuitGameEvent>,
+    mut saved_menu: ResMut<SavedMenu>,
+    #[cfg(not(target_arch = "wasm32"))] mut app_event: EventWriter<AppExit>,
+    mut cfg: ResMut<GameCfg>,
+    mut menu_state: Option<ResMut<MenuState<Screens>>>,
+    observers: Option<Res<MenuObservers>>,
+    defaults: Res<DefaultConfig>,
+) {
+    let previous = *cfg;
+    if let Some(menu_state) = menu_state.as_ref() {
+        if !action_event.is_empty() {
+            cfg.update_from_menu(menu_state.state());
+        }
+    }
+    let mut config_changed = false;
+    for event in action_event.iter() {
+        config_changed |= event.is_setter();
+        if let Some(observers) = observers.as_ref().filter(|_| cfg.share_usage_data) {
+            for observer in &observers.0 {
+                observer.on_action(event, &cfg);
+            }
+        }
+        match event {
+            Actions::NewGame => {
+                cfg.outcome = None;
+                let rng = GameRng::new(cfg.seed);
+                cfg.game_seed = rng.seed();
+                commands.insert_resource(rng);
+                start_event.send(StartGameEvent { config: *cfg });
+                fade_event.send(FadeTransition(GameState::Game))
+            }
+            Actions::Restart => {
+                cfg.outcome = None;
+                commands.insert_resource(GameRng::new(cfg.game_seed));
+                restart_event.send(RestartGameEvent);
+                fade_event.send(FadeTransition(GameState::Game))
+            }
+            Actions::ResetConfig => {
+                *cfg = cfg.reset(&defaults.0);
+                if let Some(menu_state) = menu_state.as_mut() {
+                    *menu_state.state_mut() = *cfg;
+                }
+            }
+            // `apply_theme` rebuilds the menu at `NewGame`
+            Actions::QuitToMenu => {
+                cfg.outcome = None;
+                quit_event.send(QuitGameEvent);
+                config_changed = true;
+            }
+            Actions::SaveGame(slot) => save_event.send(SaveGameEvent(SaveSlot(*slot))),
+            Actions::LoadGame(slot) => {
+                cfg.outcome = None;
+                load_event.send(LoadGameEvent(SaveSlot(*slot)));
+                fade_event.send(FadeTransition(GameState::Game))
+            }
+            Actions::Resume => {
+                // `ConfirmQuit` is only opened by the close request, and not reopened
+                saved_menu.resumed = !previous.quit_requested;
+                cfg.quit_requested = false;
+                fade_event.send(FadeTransition(GameState::Game))
+            }
+            Actions::Pause => {
+                if cfg.autosave {
+                    save_event.send(SaveGameEvent(AUTOSAVE_SLOT));
+                }
+                fade_event.send(FadeTransition(GameState::Menu))
+            }
+            // The quickmenu keeps the stack of opened screens, so going back is just a navigation.
+            // Only a close request opens `ConfirmQuit` as the root, `apply_theme` leaves it then
+            Actions::Back if previous.quit_requested => {
+                cfg.quit_requested = false;
+                config_changed = true
+            }
+            Actions::Back => navigation_event.send(NavigationEvent::Back),
+            #[cfg(not(target_arch = "wasm32"))]
+            Actions::Quit => app_event.send(AppExit),
+            // Also tracked from the window, see `window::track_window`
+            #[cfg(not(target_arch = "wasm32"))]
+            Actions::SetResolution(width, height) => cfg.resolution = (*width, *height),
+            _ => (),
+        }
+    }
+    if config_changed {
+        config_event.send(ConfigChanged {
+            previous,
+            current: *cfg,
+        });
+    }
+}
+
+/// This plugin is responsible for the game menu (containing only one button...)
+/// The menu is only drawn during the State `GameState::Menu` and is removed when that state is exited
+///
+/// `MenuPlugin::default()` is the menu of this template, the builder methods adapt it to a game,
+/// e.g. `MenuPlugin::new().initial_screen(Screens::Custom("intro"))`
+#[derive(Default, Clone)]
+pub struct MenuPlugin {
+    initial_screen: Option<Screens>,
+    default_config: Option<GameCfg>,
+    stylesheet: Option<Stylesheet>,
+}
+
+impl MenuPlugin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Root of the menu until a game is started, `NewGame` by default. The `Welcome` screen is
+    /// still shown first on the first launch
+    pub fn initial_screen(mut self, screen: Screens) -> Self {
+        self.initial_screen = Some(screen);
+        self
+    }
+    /// Used while there is no stored config, and restored by `Reset to Defaults`. Its fields
+    /// that aren't persisted, like the `title`, always apply
+    pub fn default_config(mut self, cfg: GameCfg) -> Self {
+        self.default_config = Some(cfg);
+        self
+    }
+    /// Styles every screen, instead of the `Stylesheet` built from the `MenuTheme`. The `Pause`
+    /// screen background still follows `GameCfg::pause_dim_alpha`
+    pub fn stylesheet(mut self, stylesheet: Stylesheet) -> Self {
+        self.stylesheet = Some(stylesheet);
+        self
+    }
+}
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        let mut defaults = self.default_config.unwrap_or_default();
+        if let Some(screen) = self.initial_screen {
+            defaults.initial_screen = screen;
+        }
+        app.insert_resource(DefaultConfig(defaults));
+        if let Some(stylesheet) = &self.stylesheet {
+            app.insert_resource(MenuStylesheet(stylesheet.clone()));
+        }
+        app.add_plugin(QuickMenuPlugin::<Screens>::new())
+            .add_event::<Actions>()
+            .add_event::<ConfigChanged>()
+            .add_event::<GameResultEvent>()
+            .add_event::<RestartGameEvent>()
+            .add_event::<StartGameEvent>()
+            .add_event::<QuitGameEvent>()
+            .add_event::<CustomMenuEvent>()
+            .init_resource::<ConfigSaveDelay>()
+            .init_resource::<SavedMenu>()
+            .init_resource::<scroll::FocusedButton>()
+            .init_non_send_resource::<Clipboard>()
+            .add_startup_system(config::load_config)
+            .add_startup_system(window::setup_window.in_base_set(StartupSet::PostStartup))
+            // For the Quick Menu
+            .add_startup_system(setup_menu_camera)
+            .add_system(setup_menu.in_schedule(OnExit(GameState::Loading)))
+            .add_system(cleanup_menu.in_schedule(OnExit(GameState::Menu)))
+            .add_system(tooltip::hide_tooltip.in_schedule(OnExit(GameState::Menu)))
+            .add_system(clock::pause_game_clock.in_schedule(OnEnter(GameState::Menu)))
+            .add_system(clock::unpause_game_clock.in_schedule(OnExit(GameState::Menu)))
+            .add_system(menu.in_schedule(OnEnter(GameState::Game)))
+            .add_system(menu.in_schedule(OnExit(GameState::Game)))
+            .add_system(navigation::keyboard_navigation.in_set(OnUpdate(GameState::Menu)))
+            .add_system(navigation::pause_on_key.before(handle_events))
+            .add_system(
+                navigation::step_num
+                    .before(handle_events)
+                    .in_set(OnUpdate(GameState::Menu)),
+            )
+            .add_system(
+                navigation::new_game_on_key
+                    .before(handle_events)
+                    .in_set(OnUpdate(GameState::Menu)),
+            )
+            .add_system(navigation::gamepad_navigation.before(handle_events))
+            .add_system(
+                navigation::leave_idle_game_over
+                    .before(handle_events)
+                    .in_set(OnUpdate(GameState::Menu)),
+            )
+            .add_system(
+                slider::drag_slider
+                    .before(handle_events)
+                    .in_set(OnUpdate(GameState::Menu)),
+            )
+            // Right after `bevy_ui` set the `Interaction`s, before the quickmenu reads them
+            .add_system(
+                touch::touch_taps
+                    .in_base_set(CoreSet::PreUpdate)
+                    .after(UiSystem::Focus),
+            )
+            .add_system(
+                shortcut::press_shortcuts
+                    .run_if(in_state(GameState::Menu))
+                    .in_base_set(CoreSet::PreUpdate)
+                    .after(UiSystem::Focus),
+            )
+            // Before the saving, so that the autosave is written ahead of leaving the game
+            .add_system(handle_events.before(SaveSet))
+            .add_system(share::share_config.after(handle_events))
+            .add_system(custom::forward_custom_actions)
+            .add_system(handle_game_result.in_set(OnUpdate(GameState::Game)))
+            .add_system(config::save_config)
+            .add_system(config::flush_config.in_base_set(CoreSet::Last))
+            .add_system(saves::track_saves)
+            .add_system(refresh_menu_state.in_base_set(CoreSet::PostUpdate))
+            .add_system(apply_theme.after(handle_events))
+            .add_system(background::update_background)
+            .add_system(background::despawn_background.in_schedule(OnEnter(GameState::Game)))
+            .add_system(background::update_clear_color)
+            .add_system(logo::update_title_logo)
+            .add_system(logo::despawn_title_logo.in_schedule(OnEnter(GameState::Game)))
+            .add_system(logo::animate_title_logo.in_set(OnUpdate(GameState::Menu)))
+            .add_system(window::update_window)
+            .add_system(controls::capture_rebinding.in_set(OnUpdate(GameState::Menu)))
+            .add_system(fps::toggle_fps_overlay)
+            .add_system(fps::update_fps_text)
+            .add_system(
+                tooltip::update_tooltip
+                    .after(scroll::scroll_menu)
+                    .in_set(OnUpdate(GameState::Menu)),
+            )
+            .add_system(scroll::scroll_menu.in_set(OnUpdate(GameState::Menu)));
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            app.add_startup_system(
+                window::restore_window_position.in_base_set(StartupSet::PostStartup),
+            )
+            .add_system(window::track_window)
+            .add_system(window::confirm_window_close.before(handle_events));
+        }
+
+        #[cfg(debug_assertions)]
+        {
+            app.init_resource::<debug::DebugOverlay>()
+                .add_system(debug::toggle_debug_overlay)
+                .add_system(debug::update_debug_text)
+                .add_asset::<style::MenuStyle>()
+                .init_asset_loader::<style::MenuStyleLoader>()
+                .add_startup_system(style::load_menu_style)
+                .add_system(style::apply_menu_style.after(apply_theme));
+        }
+    }
+}
@@ -0,0 +1,207 @@
+/// Additional inputs for navigating the menu. The quickmenu already moves the focus with the arrow
+/// keys or the gamepad D-pad, activates the focused item with Enter or South, and goes back with
+/// East, skipping headlines and labels. The focus itself is kept in the `MenuState`, so every input
+/// only has to be translated into a `NavigationEvent` or an `Actions`
+use super::screen::OpenScreen;
+use super::{Actions, GameCfg, Screens};
+use crate::GameState;
+use bevy::input::mouse::MouseMotion;
+use bevy::prelude::*;
+use bevy_quickmenu::{ActionTrait, MenuState, NavigationEvent};
+
+/// The `Actions` toggling between the game and the pause menu, as long as a game is in progress
+fn pause_toggle(state: &GameState, cfg: &GameCfg) -> Option<Actions> {
+    // The key is about to be bound on the `Controls` screen
+    if cfg.rebinding.is_some() {
+        return None;
+    }
+    match state {
+        // The game just ended, and is fading into the `GameOver` screen
+        _ if cfg.outcome.is_some() => None,
+        GameState::Game => Some(Actions::Pause),
+        GameState::Menu if cfg.new_game => Some(Actions::Resume),
+        _ => None,
+    }
+}
+
+/// The bound Up/Down keys, W/S by default, move the focus and Space activates the focused item,
+/// like its `Actions` being clicked
+pub fn keyboard_navigation(
+    keyboard_input: Res<Input<KeyCode>>,
+    cfg: Res<GameCfg>,
+    mut navigation_event: EventWriter<NavigationEvent>,
+) {
+    if cfg.rebinding.is_some() {
+        return;
+    }
+    if keyboard_input.just_pressed(cfg.keys.up) {
+        navigation_event.send(NavigationEvent::Up);
+    }
+    if keyboard_input.just_pressed(cfg.keys.down) {
+        navigation_event.send(NavigationEvent::Down);
+    }
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        navigation_event.send(NavigationEvent::Select);
+    }
+}
+
+/// The bound Pause key, Escape by default, toggles the pause menu, only on the press and not
+/// while it is held
+pub fn pause_on_key(
+    keyboard_input: Res<Input<KeyCode>>,
+    state: Res<State<GameState>>,
+    cfg: Res<GameCfg>,
+    mut action_event: EventWriter<Actions>,
+) {
+    if keyboard_input.just_pressed(cfg.keys.pause) {
+        if let Some(action) = pause_toggle(&state.0, &cfg) {
+            action_event.send(action);
+        }
+    }
+}
+
+/// Keys stepping `num` up, Equals being the key of Plus on US layouts, without Shift
+const INC_NUM_KEYS: [KeyCode; 3] = [KeyCode::Plus, KeyCode::Equals, KeyCode::NumpadAdd];
+const DEC_NUM_KEYS: [KeyCode; 2] = [KeyCode::Minus, KeyCode::NumpadSubtract];
+
+/// Plus and Minus, or the bumpers of any gamepad, step `num` like the `+` and `-` items, while the
+/// `Num` screen is open
+pub fn step_num(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    cfg: Res<GameCfg>,
+    menu_state: Option<ResMut<MenuState<Screens>>>,
+    open_screen: OpenScreen,
+    mut action_event: EventWriter<Actions>,
+) {
+    if cfg.rebinding.is_some() {
+        return;
+    }
+    let bumper_pressed = |button| {
+        gamepads
+            .iter()
+            .any(|gamepad| gamepad_input.just_pressed(GamepadButton::new(gamepad, button)))
+    };
+    let action = if keyboard_input.any_just_pressed(INC_NUM_KEYS)
+        || bumper_pressed(GamepadButtonType::RightTrigger)
+    {
+        Actions::IncNum
+    } else if keyboard_input.any_just_pressed(DEC_NUM_KEYS)
+        || bumper_pressed(GamepadButtonType::LeftTrigger)
+    {
+        Actions::DecNum
+    } else {
+        return;
+    };
+    if !open_screen.is(Screens::Num) {
+        return;
+    }
+    let Some(mut menu_state) = menu_state else { return };
+    action.handle(menu_state.state_mut(), &mut action_event);
+}
+
+/// The bound New Game key, F5 by default, starts a new game from any menu screen, like the
+/// `Start a New Game` item being clicked. Only while `GameCfg::dev_hotkeys` is set
+pub fn new_game_on_key(
+    keyboard_input: Res<Input<KeyCode>>,
+    cfg: Res<GameCfg>,
+    menu_state: Option<ResMut<MenuState<Screens>>>,
+    mut action_event: EventWriter<Actions>,
+) {
+    if !cfg.dev_hotkeys || cfg.rebinding.is_some() {
+        return;
+    }
+    if keyboard_input.just_pressed(cfg.keys.new_game) {
+        let Some(mut menu_state) = menu_state else { return };
+        Actions::NewGame.handle(menu_state.state_mut(), &mut action_event);
+    }
+}
+
+/// Start on any connected gamepad toggles the pause menu
+pub fn gamepad_navigation(
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    state: Res<State<GameState>>,
+    cfg: Res<GameCfg>,
+    mut action_event: EventWriter<Actions>,
+) {
+    let start_pressed = gamepads.iter().any(|gamepad| {
+        gamepad_input.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::Start))
+    });
+    if start_pressed {
+        if let Some(action) = pause_toggle(&state.0, &cfg) {
+            action_event.send(action);
+        }
+    }
+}
+
+/// Quits the `GameOver` screen to `NewGame` once nothing was pressed or moved for the
+/// `GameCfg::gameover_timeout`
+pub fn leave_idle_game_over(
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    mut motion_event: EventReader<MouseMotion>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    touches: Res<Touches>,
+    time: Res<Time>,
+    cfg: Res<GameCfg>,
+    menu_state: Option<ResMut<MenuState<Screens>>>,
+    mut action_event: EventWriter<Actions>,
+    mut idle: Local<f32>,
+) {
+    let interacted = keyboard_input.get_just_pressed().next().is_some()
+        || mouse_input.get_just_pressed().next().is_some()
+        || motion_event.iter().count() > 0
+        || gamepad_input.get_just_pressed().next().is_some()
+        || touches.any_just_pressed();
+    let Some(timeout) = cfg.gameover_timeout.filter(|_| cfg.outcome.is_some()) else {
+        *idle = 0.;
+        return;
+    };
+    if interacted {
+        *idle = 0.;
+        return;
+    }
+    *idle += time.raw_delta_seconds();
+    if *idle < timeout {
+        return;
+    }
+    *idle = 0.;
+    let Some(mut menu_state) = menu_state else { return };
+    Actions::QuitToMenu.handle(menu_state.state_mut(), &mut action_event);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pause_key_toggles_a_game_in_progress() {
+        let cfg = GameCfg {
+            new_game: true,
+            ..default()
+        };
+        assert_eq!(pause_toggle(&GameState::Game, &cfg), Some(Actions::Pause));
+        assert_eq!(pause_toggle(&GameState::Menu, &cfg), Some(Actions::Resume));
+    }
+
+    #[test]
+    fn pause_key_is_ignored_without_a_game() {
+        let main_menu = GameCfg::default();
+        assert_eq!(pause_toggle(&GameState::Menu, &main_menu), None);
+        let game_over = GameCfg {
+            new_game: true,
+            outcome: Some(false),
+            ..default()
+        };
+        assert_eq!(pause_toggle(&GameState::Menu, &game_over), None);
+        assert_eq!(pause_toggle(&GameState::Game, &game_over), None);
+        let rebinding = GameCfg {
+            new_game: true,
+            rebinding: Some(crate::actions::GameControl::Pause),
+            ..default()
+        };
+        assert_eq!(pause_toggle(&GameState::Game, &rebinding), None);
+    }
+}
@@ -0,0 +1,30 @@
+/// Hook for games collecting usage metrics of the menu, e.g. for analytics, while keeping that code
+/// out of the template
+use super::{Actions, GameCfg};
+use bevy::prelude::*;
+
+/// Gets every dispatched menu action together with the `GameCfg` it produced. Only called while
+/// the player opted in with `GameCfg::share_usage_data`, off by default
+pub trait MenuObserver: Send + Sync + 'static {
+    fn on_action(&self, action: &Actions, cfg: &GameCfg);
+}
+
+/// The observers called by `handle_events`, in the order they were added
+#[derive(Resource, Default)]
+pub struct MenuObservers(pub Vec<Box<dyn MenuObserver>>);
+
+/// Lets games add a `MenuObserver` with `app.add_menu_observer(observer)`
+pub trait AddMenuObserver {
+    fn add_menu_observer(&mut self, observer: impl MenuObserver) -> &mut Self;
+}
+
+impl AddMenuObserver for App {
+    fn add_menu_observer(&mut self, observer: impl MenuObserver) -> &mut Self {
+        self.init_resource::<MenuObservers>()
+            .world
+            .resource_mut::<MenuObservers>()
+            .0
+            .push(Box::new(observer));
+        self
+    }
+}
@@ -0,0 +1,50 @@
+/// Text drawn above the game and the menu, shared by the FPS counter and the debug overlay
+use crate::loading::FontAssets;
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+/// Where and how an overlay text is drawn
+pub struct OverlayText {
+    pub font_size: f32,
+    pub color: Color,
+    /// From the corner of the window the text sits in
+    pub position: UiRect,
+}
+
+/// Spawns the `overlay` with its `marker` while `shown`, or despawns it. The overlay waits for
+/// the font to be loaded
+pub fn toggle_overlay_text(
+    commands: &mut Commands,
+    shown: bool,
+    overlay: Option<Entity>,
+    fonts: Option<&FontAssets>,
+    text: OverlayText,
+    marker: impl Component,
+) {
+    match (shown, overlay) {
+        (true, None) => {
+            let Some(fonts) = fonts else { return };
+            commands.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: fonts.fira_sans.clone(),
+                        font_size: text.font_size,
+                        color: text.color,
+                    },
+                )
+                .with_style(Style {
+                    position_type: PositionType::Absolute,
+                    position: text.position,
+                    ..default()
+                }),
+                // Above the menu, without taking its clicks
+                ZIndex::Global(i32::MAX),
+                FocusPolicy::Pass,
+                marker,
+            ));
+        }
+        (false, Some(entity)) => commands.entity(entity).despawn_recursive(),
+        _ => (),
+    }
+}
@@ -0,0 +1,11 @@
+/// Keeps the slot timestamps shown on the `SaveGame` and `LoadGame` screens up to date
+use super::GameCfg;
+use crate::save::GameSaved;
+use bevy::prelude::*;
+
+pub fn track_saves(mut saved_event: EventReader<GameSaved>, mut cfg: ResMut<GameCfg>) {
+    for GameSaved { slot, timestamp } in saved_event.iter() {
+        let Some(saved) = cfg.saves.get_mut(slot.0 as usize) else { continue };
+        *saved = Some(*timestamp);
+    }
+}
@@ -0,0 +1,60 @@
+/// The open screen as drawn by the quickmenu, which doesn't expose its navigation stack. It draws
+/// one column per screen in the stack under its root, the last column being the open screen. Its
+/// buttons are tagged with the id of their `Menu`, which `Screens::resolve` names after the screen
+use super::Screens;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_quickmenu::{ButtonComponent, QuickMenuComponent};
+
+#[derive(SystemParam)]
+pub struct OpenScreen<'w, 's> {
+    buttons: Query<'w, 's, &'static Parent, (With<QuickMenuComponent>, With<Interaction>)>,
+    parents: Query<'w, 's, &'static Parent>,
+    children: Query<'w, 's, &'static Children>,
+    labels: Query<'w, 's, &'static Text>,
+    menus: Query<'w, 's, &'static ButtonComponent<Screens>>,
+}
+
+impl OpenScreen<'_, '_> {
+    /// The buttons of the open screen from the top, with their labels
+    pub fn buttons(&self) -> Vec<(Entity, String)> {
+        let Some(items) = self.open_column() else { return Vec::new() };
+        items
+            .iter()
+            .filter(|&&item| self.buttons.contains(item))
+            .map(|&item| (item, self.label(item).unwrap_or_default()))
+            .collect()
+    }
+
+    /// Whether the open screen is `screen`
+    pub fn is(&self, screen: Screens) -> bool {
+        let Some(items) = self.open_column() else { return false };
+        let id = format!("{screen:?}");
+        items
+            .iter()
+            .find_map(|&item| self.menus.get(item).ok())
+            .map_or(false, |button| button.menu_identifier.0 == id)
+    }
+
+    /// The items of the last column, found from any button
+    fn open_column(&self) -> Option<&Children> {
+        let column = self.buttons.iter().next()?;
+        let root = self.parents.get(column.get()).ok()?;
+        let open = *self.children.get(root.get()).ok()?.last()?;
+        self.children.get(open).ok()
+    }
+
+    /// The text of the first label of the `item`
+    pub fn label(&self, item: Entity) -> Option<String> {
+        let children = self.children.get(item).ok()?;
+        let text = children
+            .iter()
+            .find_map(|&child| self.labels.get(child).ok())?;
+        Some(
+            text.sections
+                .iter()
+                .map(|section| &*section.value)
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,245 @@
+/// Scrolling of screens taller than the window. The root of the menu is clipped to the window, so
+/// the column of a long screen is shifted up inside it, by the mouse wheel and to keep the focus of
+/// the keyboard and gamepad in view. The quickmenu doesn't expose its focus, so it is followed from
+/// the `NavigationEvent`s and the hovered item
+use bevy::ecs::event::ManualEventReader;
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_quickmenu::{NavigationEvent, QuickMenuComponent};
+
+/// Logical pixels scrolled per line of the mouse wheel
+const LINE_HEIGHT: f32 = 32.;
+
+/// A column of the menu, one for every open screen. The quickmenu redraws them on every change, so
+/// they are kept by their depth instead of by entity
+#[derive(Default, Clone, Copy)]
+pub struct ColumnScroll {
+    offset: f32,
+    /// Index of the focused button
+    focus: usize,
+    buttons: usize,
+}
+
+/// The button of the open screen the keyboard and gamepad focus is on, as followed by
+/// `scroll_menu`. Hovering a button focuses it, and moves the selection of the quickmenu along, so
+/// that both highlight the same button. `None` without a menu
+#[derive(Resource, Default)]
+pub struct FocusedButton(pub Option<Entity>);
+
+/// Scrolls the column under the mouse by the wheel, and the last column, the open screen, to its
+/// focused button when navigating
+pub fn scroll_menu(
+    mut wheel_event: EventReader<MouseWheel>,
+    mut navigation_events: ResMut<Events<NavigationEvent>>,
+    mut navigation_reader: Local<ManualEventReader<NavigationEvent>>,
+    buttons: Query<(&Parent, &Interaction), With<QuickMenuComponent>>,
+    parents: Query<&Parent>,
+    children: Query<&Children>,
+    nodes: Query<(&Node, &GlobalTransform)>,
+    mut styles: Query<&mut Style>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    mut focused: ResMut<FocusedButton>,
+    mut columns: Local<Vec<ColumnScroll>>,
+) {
+    focused.0 = None;
+    let Some((column, _)) = buttons.iter().next() else { return };
+    let Ok(root) = parents.get(column.get()).map(Parent::get) else { return };
+    let Ok(root_children) = children.get(root) else { return };
+    let Ok((root_node, _)) = nodes.get(root) else { return };
+    let viewport = root_node.size().y;
+    let scrolled: f32 = wheel_event
+        .iter()
+        .map(|wheel| match wheel.unit {
+            MouseScrollUnit::Line => wheel.y * LINE_HEIGHT,
+            MouseScrollUnit::Pixel => wheel.y,
+        })
+        .sum();
+    let steps: i32 = navigation_reader
+        .iter(&navigation_events)
+        .map(|event| match event {
+            NavigationEvent::Up => -1,
+            NavigationEvent::Down => 1,
+            _ => 0,
+        })
+        .sum();
+    let cursor = window.get_single().ok().and_then(Window::cursor_position);
+    columns.resize(root_children.len(), default());
+    let last = root_children.len().saturating_sub(1);
+    for (depth, (&column, scroll)) in root_children.iter().zip(columns.iter_mut()).enumerate() {
+        let Ok(items) = children.get(column) else { continue };
+        // Laid out from the top, so the items are measured instead of read from their transforms
+        let mut height = 0.;
+        let mut extents = Vec::new();
+        let mut hovered = None;
+        for &item in items {
+            let Ok((node, _)) = nodes.get(item) else { continue };
+            if let Ok((_, interaction)) = buttons.get(item) {
+                if *interaction == Interaction::Hovered {
+                    hovered = Some(extents.len());
+                }
+                extents.push((item, height, height + node.size().y));
+            }
+            height += node.size().y;
+        }
+        if extents.len() != scroll.buttons {
+            *scroll = ColumnScroll {
+                buttons: extents.len(),
+                ..default()
+            };
+        }
+        if let Some(hovered) = hovered {
+            // The quickmenu only selects by navigation, it is stepped to the hovered button
+            if depth == last {
+                for _ in hovered..scroll.focus {
+                    navigation_events.send(NavigationEvent::Up);
+                }
+                for _ in scroll.focus..hovered {
+                    navigation_events.send(NavigationEvent::Down);
+                }
+            }
+            scroll.focus = hovered;
+        }
+        let under_cursor = match (cursor, nodes.get(column)) {
+            (Some(cursor), Ok((node, transform))) => {
+                (cursor.x - transform.translation().x).abs() <= node.size().x / 2.
+            }
+            _ => false,
+        };
+        if under_cursor {
+            scroll.offset -= scrolled;
+        }
+        if steps != 0 && depth == last && !extents.is_empty() {
+            let count = extents.len() as i32;
+            scroll.focus = (scroll.focus as i32 + steps).rem_euclid(count) as usize;
+            let (_, top, bottom) = extents[scroll.focus];
+            if top < scroll.offset {
+                scroll.offset = top;
+            } else if bottom > scroll.offset + viewport {
+                scroll.offset = bottom - viewport;
+            }
+        }
+        if depth == last {
+            focused.0 = extents.get(scroll.focus).map(|(item, ..)| *item);
+        }
+        scroll.offset = scroll.offset.clamp(0., (height - viewport).max(0.));
+        let Ok(mut style) = styles.get_mut(column) else { continue };
+        let top = Val::Px(-scroll.offset);
+        if style.position.top != top {
+            style.position.top = top;
+        }
+    }
+    // Skips the steps to the hovered button, the focus is already on it
+    navigation_reader.iter(&navigation_events).count();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ui::flex::{flex_node_system, FlexSurface};
+
+    /// Height of the items, and of the root showing `VISIBLE` of them
+    const ITEM_HEIGHT: f32 = 40.;
+    const VISIBLE: f32 = 5.;
+
+    /// A menu root with a column of `count` items, laid out by `bevy_ui` in a window
+    fn app(count: usize) -> (App, Entity, Vec<Entity>) {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(WindowPlugin::default())
+            .add_event::<MouseWheel>()
+            .add_event::<NavigationEvent>()
+            .init_resource::<UiScale>()
+            .init_resource::<FlexSurface>()
+            .init_resource::<FocusedButton>()
+            .add_system(flex_node_system.before(scroll_menu))
+            .add_system(scroll_menu);
+        let root_style = Style {
+            size: Size::new(Val::Px(200.), Val::Px(VISIBLE * ITEM_HEIGHT)),
+            overflow: Overflow::Hidden,
+            ..default()
+        };
+        let column_style = Style {
+            flex_direction: FlexDirection::Column,
+            ..default()
+        };
+        let item_style = Style {
+            size: Size::new(Val::Px(200.), Val::Px(ITEM_HEIGHT)),
+            flex_shrink: 0.,
+            ..default()
+        };
+        let items: Vec<_> = (0..count)
+            .map(|_| {
+                let button = ButtonBundle {
+                    style: item_style.clone(),
+                    ..default()
+                };
+                app.world.spawn((button, QuickMenuComponent)).id()
+            })
+            .collect();
+        let column = app
+            .world
+            .spawn(NodeBundle {
+                style: column_style,
+                ..default()
+            })
+            .push_children(&items)
+            .id();
+        app.world
+            .spawn((
+                NodeBundle {
+                    style: root_style,
+                    ..default()
+                },
+                QuickMenuComponent,
+            ))
+            .add_child(column);
+        app.update();
+        (app, column, items)
+    }
+
+    fn focused(app: &App) -> Option<Entity> {
+        app.world.resource::<FocusedButton>().0
+    }
+
+    #[test]
+    fn hovering_focuses_the_button() {
+        let (mut app, _, items) = app(10);
+        let mut reader = app.world.resource::<Events<NavigationEvent>>().get_reader();
+        *app.world.get_mut::<Interaction>(items[5]).unwrap() = Interaction::Hovered;
+        app.update();
+        assert_eq!(focused(&app), Some(items[5]));
+        let events = app.world.resource::<Events<NavigationEvent>>();
+        let downs = reader
+            .iter(events)
+            .filter(|event| matches!(event, NavigationEvent::Down));
+        assert_eq!(downs.count(), 5);
+        // The keyboard goes on from the hovered button
+        *app.world.get_mut::<Interaction>(items[5]).unwrap() = Interaction::None;
+        app.world.send_event(NavigationEvent::Down);
+        app.update();
+        assert_eq!(focused(&app), Some(items[6]));
+    }
+
+    fn top(app: &App, column: Entity) -> Val {
+        app.world.get::<Style>(column).unwrap().position.top
+    }
+
+    #[test]
+    fn navigating_scrolls_the_focus_into_view() {
+        let count = 30;
+        let (mut app, column, items) = app(count);
+        for _ in 1..count {
+            app.world.send_event(NavigationEvent::Down);
+            app.update();
+        }
+        let scrolled = count as f32 * ITEM_HEIGHT - VISIBLE * ITEM_HEIGHT;
+        assert_eq!(top(&app, column), Val::Px(-scrolled));
+        assert_eq!(focused(&app), items.last().copied());
+        // Wraps around to the top, like the quickmenu
+        app.world.send_event(NavigationEvent::Down);
+        app.update();
+        assert_eq!(top(&app, column), Val::Px(0.));
+        assert_eq!(focused(&app), Some(items[0]));
+    }
+}
@@ -0,0 +1,83 @@
+/// Sharing of the config as a string, copied to and pasted from the clipboard from the `Settings`
+/// screen. The persisted fields are encoded as RON in base64, like `config.ron` in one line
+use super::{config, Actions, ConfigChanged, GameCfg, Screens};
+use crate::clipboard::Clipboard;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use bevy::prelude::*;
+use bevy_quickmenu::MenuState;
+
+const NO_CLIPBOARD: &str = "Clipboard unavailable";
+const INVALID_CONFIG: &str = "Invalid shared config";
+
+fn encode(cfg: &GameCfg) -> Option<String> {
+    match ron::to_string(cfg) {
+        Ok(contents) => Some(URL_SAFE_NO_PAD.encode(contents)),
+        Err(err) => {
+            warn!("Failed to serialize the config: {err}");
+            None
+        }
+    }
+}
+
+/// The config of the shared `text`, keeping the game fields and the state of the `current` game
+fn decode(text: &str, current: &GameCfg) -> Result<GameCfg, &'static str> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(text.trim())
+        .map_err(|_| INVALID_CONFIG)?;
+    let contents = String::from_utf8(bytes).map_err(|_| INVALID_CONFIG)?;
+    let mut cfg = ron::from_str::<GameCfg>(&contents).map_err(|err| {
+        warn!("Ignoring malformed shared config: {err}");
+        INVALID_CONFIG
+    })?;
+    config::migrate(&mut cfg);
+    let mut cfg = current.reset(&cfg.with_game_fields(current));
+    cfg.sanitize();
+    Ok(cfg)
+}
+
+/// Copies the config on `Actions::ExportConfig`, and applies the one in the clipboard once it is
+/// read for `Actions::ImportConfig`. Failures are shown on the `Settings` screen, by
+/// `GameCfg::clipboard_error`
+pub fn share_config(
+    mut action_event: EventReader<Actions>,
+    mut config_event: EventWriter<ConfigChanged>,
+    mut cfg: ResMut<GameCfg>,
+    menu_state: Option<ResMut<MenuState<Screens>>>,
+    mut clipboard: NonSendMut<Clipboard>,
+) {
+    let previous = *cfg;
+    let mut changed = false;
+    for event in action_event.iter() {
+        match event {
+            Actions::ExportConfig => {
+                let copied = encode(&cfg).map_or(false, |text| clipboard.write(&text));
+                cfg.clipboard_error = (!copied).then_some(NO_CLIPBOARD);
+                changed = true;
+            }
+            Actions::ImportConfig => clipboard.request_read(),
+            _ => (),
+        }
+    }
+    if let Some(text) = clipboard.take_read() {
+        match text
+            .ok_or(NO_CLIPBOARD)
+            .and_then(|text| decode(&text, &cfg))
+        {
+            Ok(imported) => *cfg = imported,
+            Err(error) => cfg.clipboard_error = Some(error),
+        }
+        changed = true;
+    }
+    if !changed {
+        return;
+    }
+    // An import replaces the fields the menu edits as well, which `refresh_menu_state` keeps
+    if let Some(mut menu_state) = menu_state {
+        *menu_state.state_mut() = *cfg;
+    }
+    config_event.send(ConfigChanged {
+        previous,
+        current: *cfg,
+    });
+}
@@ -0,0 +1,44 @@
+/// Mnemonics of menu items, pressing the key shown in the label activates the item like a click.
+/// The screens the open one was opened from stay drawn beside it, but a key only applies to the
+/// open screen, so it may be reused on other screens
+use super::screen::OpenScreen;
+use super::GameCfg;
+use bevy::prelude::*;
+use bevy_quickmenu::QuickMenuComponent;
+
+/// The `label` of an item activated by the `key`, e.g. `Resume (R)`
+pub fn with_key(label: &str, key: KeyCode) -> String {
+    format!("{label} ({key:?})")
+}
+
+/// Clicks the item of the open screen whose label ends with the pressed key. The keys navigating
+/// the menu take precedence. Right after `bevy_ui` set the `Interaction`s, like the touch taps
+pub fn press_shortcuts(
+    keyboard_input: Res<Input<KeyCode>>,
+    cfg: Res<GameCfg>,
+    open_screen: OpenScreen,
+    mut interactions: Query<&mut Interaction, With<QuickMenuComponent>>,
+) {
+    if cfg.rebinding.is_some() {
+        return;
+    }
+    let navigation = [cfg.keys.up, cfg.keys.down, cfg.keys.pause, KeyCode::Space];
+    let pressed: Vec<_> = keyboard_input
+        .get_just_pressed()
+        .filter(|key| !navigation.contains(key))
+        .collect();
+    if pressed.is_empty() {
+        return;
+    }
+    let buttons = open_screen.buttons();
+    for key in pressed {
+        let suffix = format!(" ({key:?})");
+        let Some((item, _)) = buttons.iter().find(|(_, label)| label.ends_with(&suffix)) else {
+            continue;
+        };
+        if let Ok(mut interaction) = interactions.get_mut(*item) {
+            *interaction = Interaction::Clicked;
+            return;
+        }
+    }
+}
@@ -0,0 +1,160 @@
+/// Sliders for numeric config with a wide range, drawn as a bar of text on a single menu item. The
+/// quickmenu only knows buttons, so activating the item with the keyboard or gamepad steps the
+/// value, wrapping around at the end, and holding the mouse on it drags the value to the cursor
+use super::{Actions, ConfigChanged, GameCfg, Screens};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_quickmenu::{MenuItem, MenuState, QuickMenuComponent};
+
+/// Ranges with fewer values are offered as one button per value instead
+const MIN_SLIDER_VALUES: usize = 8;
+
+/// Characters of the bar, besides the handle
+const BAR_WIDTH: usize = 10;
+
+/// A numeric value of the `GameCfg`, offered by `slider`
+pub struct Slider {
+    /// Translated like the other menu strings. The item starts with it, to be found when dragged
+    pub name: &'static str,
+    pub unit: &'static str,
+    pub step: u8,
+    pub range: fn(&GameCfg) -> (u8, u8),
+    pub value: fn(&GameCfg) -> u8,
+    pub action: fn(u8) -> Actions,
+}
+
+pub const MUSIC_VOLUME: Slider = Slider {
+    name: "Music",
+    unit: "%",
+    step: 5,
+    range: |_| (0, 100),
+    value: |cfg| (cfg.music_volume * 100.).round() as u8,
+    action: Actions::SetMusicVolume,
+};
+
+pub const SFX_VOLUME: Slider = Slider {
+    name: "SFX",
+    unit: "%",
+    step: 5,
+    range: |_| (0, 100),
+    value: |cfg| (cfg.sfx_volume * 100.).round() as u8,
+    action: Actions::SetSfxVolume,
+};
+
+pub const NUM: Slider = Slider {
+    name: "Num",
+    unit: "",
+    step: 1,
+    range: |cfg| (cfg.num_min, cfg.num_max),
+    value: |cfg| cfg.num,
+    action: Actions::SetNum,
+};
+
+/// The sliders `drag_slider` looks for
+const SLIDERS: [&Slider; 3] = [&MUSIC_VOLUME, &SFX_VOLUME, &NUM];
+
+/// The item of the `slider`, or the buttons of its values for a small range. The buttons are not
+/// named, so the screen labels them itself
+pub fn slider(cfg: &GameCfg, slider: &Slider) -> Vec<MenuItem<Screens>> {
+    let (min, max) = (slider.range)(cfg);
+    let value = (slider.value)(cfg);
+    let step = slider.step.max(1);
+    let values = (min..=max).step_by(step as usize);
+    if values.len() < MIN_SLIDER_VALUES {
+        return values
+            .map(|x| {
+                MenuItem::action(format!("{x}{}", slider.unit), (slider.action)(x))
+                    .checked(value == x)
+            })
+            .collect();
+    }
+    let filled = (value.clamp(min, max) - min) as usize * BAR_WIDTH / (max - min) as usize;
+    let bar = format!("{}O{}", "=".repeat(filled), "-".repeat(BAR_WIDTH - filled));
+    let next = if value >= max {
+        min
+    } else {
+        value.saturating_add(step).min(max)
+    };
+    let label = format!("{}: {bar} {value}{}", cfg.tr(slider.name), slider.unit);
+    vec![MenuItem::action(label, (slider.action)(next))]
+}
+
+/// The slider held with the mouse, and its item from the press on
+#[derive(Clone, Copy)]
+pub struct Drag {
+    slider: &'static Slider,
+    left: f32,
+    width: f32,
+    /// Where the cursor was last, `None` until it is read
+    value: Option<u8>,
+}
+
+/// Moves the slider pressed with the mouse to the position of the cursor along its item, for as
+/// long as the button is held. Only the open screen follows the cursor, the value is set once the
+/// button is released, without an `Actions` event, so that the click and the ducking aren't
+/// repeated for every step. The item is redrawn with every change, so its position is kept from
+/// the press
+pub fn drag_slider(
+    mouse_input: Res<Input<MouseButton>>,
+    mut cfg: ResMut<GameCfg>,
+    items: Query<(&Interaction, &Node, &GlobalTransform, &Children), With<QuickMenuComponent>>,
+    labels: Query<&Text>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    menu_state: Option<ResMut<MenuState<Screens>>>,
+    mut config_event: EventWriter<ConfigChanged>,
+    mut dragged: Local<Option<Drag>>,
+) {
+    if !mouse_input.pressed(MouseButton::Left) {
+        let released = dragged
+            .take()
+            .and_then(|drag| Some((drag.slider, drag.value?)));
+        let Some((slider, value)) = released else { return };
+        let previous = *cfg;
+        (slider.action)(value).apply(&mut cfg);
+        if (slider.value)(&previous) != (slider.value)(&cfg) {
+            config_event.send(ConfigChanged {
+                previous,
+                current: *cfg,
+            });
+        }
+        return;
+    }
+    if dragged.is_none() {
+        *dragged = items
+            .iter()
+            .filter(|(interaction, ..)| **interaction == Interaction::Clicked)
+            .find_map(|(_, node, transform, children)| {
+                let text = children.iter().find_map(|&child| labels.get(child).ok())?;
+                let label: String = text
+                    .sections
+                    .iter()
+                    .map(|section| &*section.value)
+                    .collect();
+                let slider = SLIDERS
+                    .into_iter()
+                    .find(|slider| label.starts_with(&format!("{}:", cfg.tr(slider.name))))?;
+                // Both the UI and the cursor are in logical pixels from the left
+                let width = node.size().x;
+                let left = transform.translation().x - width / 2.;
+                (width > 0.).then_some(Drag {
+                    slider,
+                    left,
+                    width,
+                    value: None,
+                })
+            });
+    }
+    let Some(drag) = dragged.as_mut() else { return };
+    let Some(cursor) = window.get_single().ok().and_then(Window::cursor_position) else { return };
+    let fraction = ((cursor.x - drag.left) / drag.width).clamp(0., 1.);
+    let (min, max) = (drag.slider.range)(&cfg);
+    let step = drag.slider.step.max(1) as f32;
+    let steps = (fraction * (max - min) as f32 / step).round();
+    let value = (min as f32 + steps * step).min(max as f32) as u8;
+    drag.value = Some(value);
+    // Also undoes the step of the press, which the quickmenu activates the item with
+    let Some(mut menu_state) = menu_state else { return };
+    if value != (drag.slider.value)(menu_state.state()) {
+        (drag.slider.action)(value).apply(menu_state.state_mut());
+    }
+}
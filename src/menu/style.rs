@@ -0,0 +1,105 @@
+/// Menu colors read from `MENU_STYLE`, to tweak them without recompiling. Only compiled into debug
+/// builds, the file is watched for changes and reapplied on every save. Without the file the
+/// `Theme` picked on the `Theme` screen is used, as in release builds
+use super::{root_menu, GameCfg, MenuAssets, MenuTheme, Screens};
+use crate::GameState;
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use bevy_quickmenu::MenuState;
+use serde::Deserialize;
+use std::path::Path;
+
+/// Relative to the assets folder. Holds the colors of a `MenuTheme` in RON, e.g.
+/// `(background: Rgba(red: 0., green: 0., blue: 0.2, alpha: 1.), text: .., ..)`
+const MENU_STYLE: &str = "menu_style.ron";
+
+/// The fields of a `MenuTheme`
+#[derive(Deserialize, TypeUuid)]
+#[uuid = "6b9f7c4e-2d1a-4f3b-9e5c-8a7d0b1c2e3f"]
+pub struct MenuStyle {
+    background: Color,
+    text: Color,
+    highlight: Color,
+    headline: Color,
+    #[serde(default)]
+    clear: Option<Color>,
+    /// Fall back to the `background`
+    #[serde(default)]
+    victory: Option<Color>,
+    #[serde(default)]
+    defeat: Option<Color>,
+}
+
+impl MenuStyle {
+    fn menu_theme(&self) -> MenuTheme {
+        MenuTheme {
+            background: self.background,
+            text: self.text,
+            highlight: self.highlight,
+            headline: self.headline,
+            clear: self.clear,
+            victory: self.victory.unwrap_or(self.background),
+            defeat: self.defeat.unwrap_or(self.background),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct MenuStyleLoader;
+
+impl AssetLoader for MenuStyleLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let style = ron::de::from_bytes::<MenuStyle>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(style));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+}
+
+/// Keeps the `MENU_STYLE` loaded, so that its changes are picked up
+#[derive(Resource)]
+pub struct MenuStyleHandle(Handle<MenuStyle>);
+
+pub fn load_menu_style(mut commands: Commands, asset_server: Res<AssetServer>) {
+    // A missing file is not an error, it just keeps the coded `Theme`
+    if !asset_server.asset_io().is_file(Path::new(MENU_STYLE)) {
+        return;
+    }
+    commands.insert_resource(MenuStyleHandle(asset_server.load(MENU_STYLE)));
+}
+
+/// Applies the `MENU_STYLE` once it is loaded and whenever it changes, rebuilding the menu at its
+/// root screen like `apply_theme`. Picking a `Theme` overrides it until the next change of the file
+pub(super) fn apply_menu_style(
+    mut commands: Commands,
+    mut style_event: EventReader<AssetEvent<MenuStyle>>,
+    styles: Res<Assets<MenuStyle>>,
+    state: Res<State<GameState>>,
+    cfg: Res<GameCfg>,
+    menu_state: Option<Res<MenuState<Screens>>>,
+    assets: MenuAssets,
+) {
+    for event in style_event.iter() {
+        let (AssetEvent::Created { handle } | AssetEvent::Modified { handle }) = event else {
+            continue;
+        };
+        let Some(style) = styles.get(handle) else { continue };
+        let theme = style.menu_theme();
+        // Before loading is done the menu is built by `setup_menu`, from the `MenuTheme`
+        if menu_state.is_some() {
+            commands.insert_resource(root_menu(&state.0, &cfg, &theme, &assets));
+        }
+        commands.insert_resource(theme);
+    }
+}
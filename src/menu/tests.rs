@@ -0,0 +1,413 @@
+/// A headless `App` for tests of the menu: the `MenuPlugin` on `MinimalPlugins`, with the input
+/// and window events but without a window, a renderer or audio. Loading is skipped by stubbing the
+/// `FontAssets`. Items are activated by `press`, like a click on them
+use super::*;
+use crate::cleanup::{CleanupPlugin, GameEntity};
+use crate::loading::FontAssets;
+use crate::save::SavePlugin;
+use crate::storage;
+use crate::transition::{FadeDuration, TransitionPlugin};
+use bevy::diagnostic::DiagnosticsPlugin;
+use bevy::ecs::system::SystemState;
+use bevy::input::InputPlugin;
+use bevy::window::ExitCondition;
+use bevy::winit::WinitWindows;
+
+/// In `GameState::Menu` at the `NewGame` screen, like after loading on a later launch
+pub(crate) fn app() -> App {
+    storage::clear();
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(InputPlugin)
+        .add_plugin(WindowPlugin {
+            primary_window: None,
+            exit_condition: ExitCondition::DontExit,
+            close_when_requested: false,
+        })
+        .add_plugin(DiagnosticsPlugin)
+        .add_asset::<Font>()
+        .init_resource::<UiScale>()
+        .init_resource::<ClearColor>()
+        .insert_non_send_resource(WinitWindows::default())
+        .add_state::<GameState>()
+        .add_plugin(MenuPlugin::default())
+        .add_plugin(TransitionPlugin)
+        .add_plugin(SavePlugin)
+        .insert_resource(FadeDuration(0.))
+        .insert_resource(FontAssets {
+            fira_sans: Handle::default(),
+        });
+    // Runs the startup systems, which load the config
+    app.update();
+    app.world.resource_mut::<GameCfg>().seen_welcome = true;
+    app.world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Menu);
+    settle(&mut app);
+    app
+}
+
+/// Runs the frames of a fade, and of the state transition it ends with
+pub(crate) fn settle(app: &mut App) {
+    for _ in 0..5 {
+        app.update();
+    }
+}
+
+/// Activates the item of `action` on the open screen and waits for its fade, if any
+pub(crate) fn press(app: &mut App, action: Actions) {
+    {
+        let mut system_state =
+            SystemState::<(ResMut<MenuState<Screens>>, EventWriter<Actions>)>::new(&mut app.world);
+        let (mut menu_state, mut action_event) = system_state.get_mut(&mut app.world);
+        action.handle(menu_state.state_mut(), &mut action_event);
+    }
+    settle(app);
+}
+
+pub(crate) fn state(app: &App) -> GameState {
+    app.world.resource::<State<GameState>>().0
+}
+
+pub(crate) fn cfg(app: &App) -> GameCfg {
+    *app.world.resource::<GameCfg>()
+}
+
+#[test]
+fn new_game_switches_to_the_game() {
+    let mut app = app();
+    press(&mut app, Actions::NewGame);
+    assert_eq!(state(&app), GameState::Game);
+    assert!(cfg(&app).new_game);
+    assert_ne!(cfg(&app).game_seed, 0);
+}
+
+#[test]
+fn resume_needs_a_game_in_progress() {
+    let mut app = app();
+    press(&mut app, Actions::Resume);
+    assert_eq!(state(&app), GameState::Menu);
+}
+
+#[test]
+fn pause_and_resume_switch_between_the_game_and_the_menu() {
+    let mut app = app();
+    press(&mut app, Actions::NewGame);
+    press(&mut app, Actions::Pause);
+    assert_eq!(state(&app), GameState::Menu);
+    assert!(cfg(&app).new_game);
+    press(&mut app, Actions::Resume);
+    assert_eq!(state(&app), GameState::Game);
+}
+
+#[test]
+fn set_boolean_toggles_the_flag_in_the_menu() {
+    let mut app = app();
+    let boolean = cfg(&app).flags.boolean;
+    press(&mut app, Actions::SetFlag("Boolean"));
+    assert_eq!(cfg(&app).flags.boolean, !boolean);
+    press(&mut app, Actions::SetFlag("Boolean"));
+    assert_eq!(cfg(&app).flags.boolean, boolean);
+    assert_eq!(state(&app), GameState::Menu);
+}
+
+#[test]
+fn set_num_sets_the_num_in_the_menu() {
+    let mut app = app();
+    press(&mut app, Actions::SetNum(5));
+    assert_eq!(cfg(&app).num, 5);
+    assert_eq!(state(&app), GameState::Menu);
+}
+
+#[test]
+fn only_the_config_fields_are_persisted() {
+    let cfg = GameCfg {
+        num: 5,
+        muted: true,
+        new_game: true,
+        outcome: Some(true),
+        ..default()
+    };
+    let stored: GameCfg = ron::from_str(&ron::to_string(&cfg).unwrap()).unwrap();
+    assert_eq!(stored.num, 5);
+    assert!(stored.muted);
+    assert!(!stored.new_game);
+    assert_eq!(stored.outcome, None);
+}
+
+#[test]
+fn reset_keeps_the_game_in_progress() {
+    let cfg = GameCfg {
+        num: 5,
+        new_game: true,
+        game_seed: 7,
+        ..default()
+    };
+    let reset = cfg.reset(&GameCfg::default());
+    assert_eq!(reset.num, GameCfg::default().num);
+    assert!(reset.new_game);
+    assert_eq!(reset.game_seed, 7);
+}
+
+/// Like the UI the quickmenu builds for a screen, a root with an item
+fn spawn_menu_ui(app: &mut App) {
+    app.world
+        .spawn((NodeBundle::default(), QuickMenuComponent))
+        .with_children(|parent| {
+            parent.spawn((NodeBundle::default(), QuickMenuComponent));
+        });
+}
+
+#[test]
+fn menu_ui_is_removed_when_leaving_the_menu() {
+    let mut app = app();
+    let mut menu_ui = app
+        .world
+        .query_filtered::<Entity, With<QuickMenuComponent>>();
+    // Leaves the `NewGame` screen, then the `Pause` screen
+    for leave in [Actions::NewGame, Actions::Resume, Actions::Resume] {
+        spawn_menu_ui(&mut app);
+        let shown: Vec<_> = menu_ui.iter(&app.world).collect();
+        press(&mut app, leave);
+        assert_eq!(state(&app), GameState::Game);
+        let removed = |entity: &Entity| app.world.get_entity(*entity).is_none();
+        assert!(shown.iter().all(removed));
+        press(&mut app, Actions::Pause);
+    }
+}
+
+#[test]
+fn pause_and_resume_keep_the_game() {
+    let mut app = app();
+    press(&mut app, Actions::NewGame);
+    // Set by the game, which the menu doesn't know about
+    app.world.resource_mut::<GameCfg>().outcome = Some(true);
+    press(&mut app, Actions::Pause);
+    assert_eq!(state(&app), GameState::Menu);
+    press(&mut app, Actions::Resume);
+    assert_eq!(state(&app), GameState::Game);
+    assert!(cfg(&app).new_game);
+    assert_eq!(cfg(&app).outcome, Some(true));
+}
+
+#[test]
+fn fields_set_outside_of_the_menu_survive_its_actions() {
+    let mut app = app();
+    app.world.resource_mut::<GameCfg>().saves[0] = Some(42);
+    settle(&mut app);
+    press(&mut app, Actions::SetFlag("Boolean"));
+    assert!(!cfg(&app).flags.boolean);
+    assert_eq!(cfg(&app).saves[0], Some(42));
+    // And are shown in the menu
+    let menu_state = app.world.resource::<MenuState<Screens>>();
+    assert_eq!(menu_state.state().saves[0], Some(42));
+}
+
+#[test]
+fn set_num_is_clamped_to_the_offered_range() {
+    let mut app = app();
+    press(&mut app, Actions::SetNum(9));
+    assert_eq!(cfg(&app).num, cfg(&app).num_max);
+    press(&mut app, Actions::SetNum(0));
+    assert_eq!(cfg(&app).num, cfg(&app).num_min);
+}
+
+#[test]
+fn background_follows_the_root_screen() {
+    let mut app = app();
+    let mut backgrounds = MenuBackgrounds::default();
+    backgrounds.0.insert(Screens::Pause, Handle::default());
+    app.insert_resource(backgrounds);
+    let mut shown = app
+        .world
+        .query_filtered::<(), With<background::MenuBackground>>();
+    press(&mut app, Actions::NewGame);
+    for _ in 0..2 {
+        press(&mut app, Actions::Pause);
+        assert_eq!(shown.iter(&app.world).count(), 1);
+        press(&mut app, Actions::Resume);
+        assert_eq!(shown.iter(&app.world).count(), 0);
+    }
+}
+
+#[test]
+fn title_logo_is_only_shown_at_new_game() {
+    let mut app = app();
+    app.insert_resource(crate::loading::TextureAssets {
+        texture_bevy: Handle::default(),
+        icon: Handle::default(),
+    });
+    let mut shown = app.world.query_filtered::<(), With<logo::TitleLogo>>();
+    app.update();
+    assert_eq!(shown.iter(&app.world).count(), 1);
+    press(&mut app, Actions::NewGame);
+    assert_eq!(shown.iter(&app.world).count(), 0);
+    press(&mut app, Actions::Pause);
+    assert_eq!(shown.iter(&app.world).count(), 0);
+}
+
+#[test]
+fn pausing_stops_the_game_clock() {
+    let mut app = app();
+    press(&mut app, Actions::NewGame);
+    press(&mut app, Actions::Pause);
+    let paused_at = app.world.resource::<Time>().elapsed();
+    settle(&mut app);
+    assert!(app.world.resource::<Time>().is_paused());
+    assert_eq!(app.world.resource::<Time>().elapsed(), paused_at);
+    press(&mut app, Actions::Resume);
+    assert!(!app.world.resource::<Time>().is_paused());
+}
+
+#[test]
+fn runs_without_a_window() {
+    let mut app = app();
+    press(&mut app, Actions::NewGame);
+    press(&mut app, Actions::Pause);
+    let mut windows = app.world.query_filtered::<(), With<PrimaryWindow>>();
+    assert_eq!(windows.iter(&app.world).count(), 0);
+    let menu_state = app.world.resource::<MenuState<Screens>>();
+    assert!(menu_state.state().new_game);
+}
+
+#[test]
+fn sanitize_brings_fields_into_range() {
+    let mut cfg = GameCfg {
+        music_volume: 1.5,
+        sfx_volume: -0.5,
+        num: 200,
+        gameover_timeout: Some(-1.),
+        outcome: Some(false),
+        ..default()
+    };
+    cfg.sanitize();
+    assert_eq!(cfg.music_volume, 1.);
+    assert_eq!(cfg.sfx_volume, 0.);
+    assert_eq!(cfg.num, cfg.num_max);
+    assert_eq!(cfg.gameover_timeout, None);
+    assert_eq!(cfg.outcome, None);
+}
+
+#[test]
+fn clear_color_waits_for_the_theme() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_state::<GameState>()
+        .init_resource::<ClearColor>()
+        .insert_resource(GameCfg::default())
+        .add_system(background::update_clear_color);
+    app.update();
+    assert_eq!(state(&app), GameState::Loading);
+}
+
+#[test]
+fn difficulty_presets_are_told_apart() {
+    assert_eq!(GameCfg::default().difficulty(), Difficulty::Normal);
+    // Wide enough for every preset
+    let range = GameCfg {
+        num_min: 1,
+        ..default()
+    };
+    for difficulty in Difficulty::ALL {
+        let mut cfg = range;
+        difficulty.apply(&mut cfg);
+        assert_eq!(Difficulty::of(&cfg), Some(difficulty));
+        assert_eq!(Difficulty::from_num(&cfg), difficulty);
+    }
+}
+
+#[test]
+fn difficulty_presets_stay_in_the_offered_range() {
+    let mut cfg = GameCfg {
+        num_min: 4,
+        num_max: 4,
+        ..default()
+    };
+    Difficulty::Easy.apply(&mut cfg);
+    assert_eq!(cfg.num, 4);
+    assert_eq!(Difficulty::of(&cfg), Some(Difficulty::Easy));
+}
+
+/// The `num` of every `StartGameEvent`
+#[derive(Resource, Default)]
+struct Started(Vec<u8>);
+
+fn record_starts(mut start_event: EventReader<StartGameEvent>, mut started: ResMut<Started>) {
+    started
+        .0
+        .extend(start_event.iter().map(|event| event.config.num));
+}
+
+#[test]
+fn new_game_starts_with_the_config_of_the_menu() {
+    let mut app = app();
+    app.init_resource::<Started>().add_system(record_starts);
+    press(&mut app, Actions::SetNum(5));
+    press(&mut app, Actions::NewGame);
+    assert_eq!(state(&app), GameState::Game);
+    assert!(cfg(&app).new_game);
+    assert_ne!(cfg(&app).game_seed, 0);
+    assert_eq!(app.world.resource::<Started>().0, [5]);
+}
+
+/// A column of buttons, the way the quickmenu draws a screen
+fn spawn_column(parent: &mut WorldChildBuilder, labels: &[&str]) -> Vec<Entity> {
+    let mut buttons = Vec::new();
+    parent.spawn(NodeBundle::default()).with_children(|column| {
+        for label in labels {
+            let button = column
+                .spawn((ButtonBundle::default(), QuickMenuComponent))
+                .with_children(|button| {
+                    button.spawn(TextBundle::from_section(*label, default()));
+                })
+                .id();
+            buttons.push(button);
+        }
+    });
+    buttons
+}
+
+#[test]
+fn shortcuts_only_apply_to_the_open_screen() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .init_resource::<Input<KeyCode>>()
+        .insert_resource(GameCfg::default())
+        .add_system(shortcut::press_shortcuts);
+    let (mut behind, mut open) = (Vec::new(), Vec::new());
+    app.world
+        .spawn((NodeBundle::default(), QuickMenuComponent))
+        .with_children(|root| {
+            behind = spawn_column(root, &["Load Game (L)", "Quit (Q)"]);
+            open = spawn_column(root, &["Slot 1", "Load Game (L)"]);
+        });
+    let interaction = |app: &App, entity: Entity| *app.world.get::<Interaction>(entity).unwrap();
+    app.world.resource_mut::<Input<KeyCode>>().press(KeyCode::Q);
+    app.update();
+    assert_eq!(interaction(&app, behind[1]), Interaction::None);
+    let mut keyboard_input = app.world.resource_mut::<Input<KeyCode>>();
+    keyboard_input.clear();
+    keyboard_input.press(KeyCode::L);
+    app.update();
+    assert_eq!(interaction(&app, behind[0]), Interaction::None);
+    assert_eq!(interaction(&app, open[1]), Interaction::Clicked);
+}
+
+#[test]
+fn game_entities_are_kept_until_the_game_is_left() {
+    let mut app = app();
+    app.add_plugin(CleanupPlugin);
+    let mut entities = app.world.query_filtered::<(), With<GameEntity>>();
+    press(&mut app, Actions::NewGame);
+    // The new and the loaded game are played right away, and left in turn
+    for leave in [Actions::NewGame, Actions::LoadGame(1), Actions::QuitToMenu] {
+        app.world.spawn(GameEntity);
+        press(&mut app, Actions::Pause);
+        press(&mut app, Actions::Resume);
+        assert_eq!(entities.iter(&app.world).count(), 1);
+        press(&mut app, Actions::Pause);
+        press(&mut app, leave);
+        assert_eq!(entities.iter(&app.world).count(), 0);
+    }
+}
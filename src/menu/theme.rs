@@ -0,0 +1,64 @@
+/// Colors of the menu, selected on the `Theme` screen
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The colors the `Stylesheet` of the menu is built from
+#[derive(Resource, Clone, Copy)]
+pub struct MenuTheme {
+    pub background: Color,
+    pub text: Color,
+    /// Background of the focused item
+    pub highlight: Color,
+    pub headline: Color,
+    /// `ClearColor` of the window while a menu screen is open, `None` keeps the one of the game.
+    /// The `Pause` screen always keeps it, as the game shows through there
+    pub clear: Option<Color>,
+    /// Backgrounds of the `GameOver` screen, by the outcome of the game
+    pub victory: Color,
+    pub defeat: Color,
+}
+
+pub const DARK: MenuTheme = MenuTheme {
+    background: Color::BLACK,
+    text: Color::WHITE,
+    highlight: Color::DARK_GRAY,
+    headline: Color::GOLD,
+    clear: None,
+    victory: Color::rgb(0.3, 0.22, 0.),
+    defeat: Color::rgb(0.3, 0., 0.),
+};
+
+pub const LIGHT: MenuTheme = MenuTheme {
+    background: Color::WHITE,
+    text: Color::BLACK,
+    highlight: Color::SILVER,
+    headline: Color::MIDNIGHT_BLUE,
+    clear: None,
+    victory: Color::rgb(1., 0.93, 0.7),
+    defeat: Color::rgb(1., 0.8, 0.8),
+};
+
+/// The built-in `MenuTheme`s
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl Theme {
+    pub const ALL: [Self; 2] = [Self::Dark, Self::Light];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Dark => "Dark",
+            Self::Light => "Light",
+        }
+    }
+    pub fn menu_theme(&self) -> MenuTheme {
+        match self {
+            Self::Dark => DARK,
+            Self::Light => LIGHT,
+        }
+    }
+}
@@ -0,0 +1,152 @@
+/// Tooltips explaining menu items, shown next to the mouse while it hovers over them, or next to
+/// the item with the keyboard or gamepad focus
+use super::scroll::FocusedButton;
+use super::GameCfg;
+use crate::loading::FontAssets;
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+use bevy::window::PrimaryWindow;
+use bevy_quickmenu::QuickMenuComponent;
+
+/// A menu item explained by a tooltip. Both are translated like the other menu strings, and the
+/// screens build the item with the `label`, so that it is found by it
+pub struct Explained {
+    pub label: &'static str,
+    pub tooltip: &'static str,
+}
+
+pub const AUTOSAVE: Explained = Explained {
+    label: "Autosave",
+    tooltip: "Saves to the autosave slot whenever the game is paused",
+};
+
+pub const COLORBLIND_MODE: Explained = Explained {
+    label: "Colorblind Mode",
+    tooltip: "Highlights the focused item by contrast instead of color",
+};
+
+pub const SHARE_USAGE_DATA: Explained = Explained {
+    label: "Share Usage Data",
+    tooltip: "Reports the menu actions to the game, off by default",
+};
+
+pub const FIT_TO_SCREEN: Explained = Explained {
+    label: "Fit to Screen",
+    tooltip: "A borderless window covering the monitor",
+};
+
+pub const SEED: Explained = Explained {
+    label: "Seed",
+    tooltip: "0 picks a random seed for every new game",
+};
+
+/// The items `update_tooltip` looks for
+const EXPLAINED: [&Explained; 5] = [
+    &AUTOSAVE,
+    &COLORBLIND_MODE,
+    &SHARE_USAGE_DATA,
+    &FIT_TO_SCREEN,
+    &SEED,
+];
+
+/// Offset of the tooltip from the mouse or the item, so that neither covers it
+const CURSOR_OFFSET: f32 = 16.;
+
+#[derive(Component)]
+pub struct Tooltip;
+
+/// The tooltip of the item labeled `label`. The label may be preceded by its check mark
+fn tooltip(cfg: &GameCfg, label: &str) -> Option<&'static str> {
+    EXPLAINED
+        .into_iter()
+        .find(|item| label.ends_with(cfg.tr(item.label)))
+        .map(|item| cfg.tr(item.tooltip))
+}
+
+pub fn hide_tooltip(mut commands: Commands, shown: Query<Entity, With<Tooltip>>) {
+    for entity in &shown {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Shows the tooltip of the hovered item next to the mouse, or else of the focused item next to
+/// it, and removes it when neither has one
+pub fn update_tooltip(
+    mut commands: Commands,
+    cfg: Res<GameCfg>,
+    ui_scale: Res<UiScale>,
+    fonts: Option<Res<FontAssets>>,
+    focused_button: Res<FocusedButton>,
+    items: Query<(&Interaction, &Node, &GlobalTransform, &Children), With<QuickMenuComponent>>,
+    labels: Query<&Text, Without<Tooltip>>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    mut shown: Query<(Entity, &mut Text, &mut Style), With<Tooltip>>,
+) {
+    let label = |children: &Children| {
+        let text = children.iter().find_map(|&child| labels.get(child).ok())?;
+        let label: String = text
+            .sections
+            .iter()
+            .map(|section| &*section.value)
+            .collect();
+        tooltip(&cfg, &label)
+    };
+    // The cursor position starts at the bottom left, the UI at the top left and scaled
+    let scale = ui_scale.scale as f32;
+    let window = window.get_single().ok();
+    let hovered = items
+        .iter()
+        .find(|(interaction, ..)| **interaction == Interaction::Hovered)
+        .and_then(|(.., children)| label(children))
+        .zip(window.and_then(|window| {
+            let cursor = window.cursor_position()?;
+            Some(Vec2::new(cursor.x, window.height() - cursor.y) / scale)
+        }));
+    // Beside the top right corner of the focused item, its transform being the center
+    let focused = || {
+        let (_, node, transform, children) = items.get(focused_button.0?).ok()?;
+        let corner = transform.translation().truncate() + node.size() * Vec2::new(0.5, -0.5);
+        Some((label(children)?, corner / scale))
+    };
+    let Some((text, anchor)) = hovered.or_else(focused) else {
+        if let Ok((entity, ..)) = shown.get_single() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    };
+    let position = UiRect {
+        left: Val::Px(anchor.x + CURSOR_OFFSET),
+        top: Val::Px(anchor.y + CURSOR_OFFSET),
+        ..default()
+    };
+    if let Ok((_, mut shown_text, mut style)) = shown.get_single_mut() {
+        shown_text.sections[0].value = text.to_string();
+        style.position = position;
+        return;
+    }
+    // The tooltip waits for the font to be loaded
+    let Some(fonts) = fonts else { return };
+    commands.spawn((
+        TextBundle {
+            // Above the menu, without taking its hover
+            z_index: ZIndex::Global(i32::MAX),
+            focus_policy: FocusPolicy::Pass,
+            background_color: Color::rgba(0., 0., 0., 0.8).into(),
+            ..TextBundle::from_section(
+                text,
+                TextStyle {
+                    font: fonts.fira_sans.clone(),
+                    font_size: 16.,
+                    color: Color::WHITE,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position,
+                max_size: Size::new(Val::Px(240.), Val::Undefined),
+                ..default()
+            })
+        },
+        Tooltip,
+    ));
+}
@@ -0,0 +1,52 @@
+/// Taps on touchscreens. `bevy_ui` treats a touch like the mouse, clicking the item under it right
+/// when it is pressed. This would activate items when the player only meant to drag, so the click
+/// is held back until the touch is released as a tap
+use bevy::prelude::*;
+use bevy_quickmenu::QuickMenuComponent;
+
+/// Farthest distance in logical pixels a touch may move, and still be a tap
+const TAP_SLOP: f32 = 16.;
+
+/// Moves the `Interaction::Clicked` set on the press of a touch to its release. The item is found
+/// by the same hit-testing as for the mouse, as `bevy_ui` has done it on the press. A second touch
+/// cancels the tap, so that every tap fires at most one action
+pub fn touch_taps(
+    touches: Res<Touches>,
+    mut buttons: Query<(Entity, &mut Interaction), With<QuickMenuComponent>>,
+    mut tap: Local<Option<(u64, Entity)>>,
+) {
+    if touches.any_just_pressed() {
+        let mut clicked = None;
+        for (entity, mut interaction) in &mut buttons {
+            if *interaction == Interaction::Clicked {
+                *interaction = Interaction::None;
+                clicked.get_or_insert(entity);
+            }
+        }
+        let touch = touches.iter_just_pressed().next();
+        *tap = match (touch, clicked) {
+            (Some(touch), Some(entity)) if touches.iter().count() == 1 => {
+                Some((touch.id(), entity))
+            }
+            _ => None,
+        };
+    }
+    if touches.iter().count() > 1 {
+        *tap = None;
+    }
+    let Some((id, entity)) = *tap else { return };
+    if let Some(touch) = touches.get_pressed(id) {
+        if touch.distance().length() > TAP_SLOP {
+            *tap = None;
+        }
+        return;
+    }
+    *tap = None;
+    let Some(touch) = touches.get_released(id) else { return };
+    if touch.distance().length() > TAP_SLOP {
+        return;
+    }
+    if let Ok((_, mut interaction)) = buttons.get_mut(entity) {
+        *interaction = Interaction::Clicked;
+    }
+}
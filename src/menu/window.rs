@@ -0,0 +1,251 @@
+/// Applies the display related fields of `GameCfg` to the `PrimaryWindow` and the `UiScale`. On
+/// desktop the window size and position are also tracked, to restore them on the next launch
+use super::{tooltip, ConfigChanged, GameCfg};
+#[cfg(not(target_arch = "wasm32"))]
+use super::{root_menu, Actions, MenuAssets, MenuTheme};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::GameState;
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::app::AppExit;
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::window::{
+    MonitorSelection, WindowCloseRequested, WindowMode, WindowMoved, WindowPosition, WindowResized,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::winit::WinitWindows;
+use serde::{Deserialize, Serialize};
+
+/// How the window is shown, picked on the `DisplayMode` screen
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    Fullscreen,
+    /// A borderless window covering the monitor, so that switching to other windows stays fast.
+    /// Desktop only, wasm falls back to `Windowed`
+    FitToScreen,
+}
+
+impl DisplayMode {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub const ALL: [Self; 3] = [Self::Windowed, Self::Fullscreen, Self::FitToScreen];
+    #[cfg(target_arch = "wasm32")]
+    pub const ALL: [Self; 2] = [Self::Windowed, Self::Fullscreen];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Windowed => "Windowed",
+            Self::Fullscreen => "Fullscreen",
+            Self::FitToScreen => tooltip::FIT_TO_SCREEN.label,
+        }
+    }
+}
+
+/// Applies the stored display config, once the window exists
+pub fn setup_window(
+    cfg: Res<GameCfg>,
+    mut ui_scale: ResMut<UiScale>,
+    mut window: Query<&mut Window, With<PrimaryWindow>>,
+    #[cfg(not(target_arch = "wasm32"))] monitor: CurrentMonitor,
+) {
+    ui_scale.scale = cfg.ui_scale as f64;
+    let Ok(mut window) = window.get_single_mut() else { return };
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        window.present_mode = cfg.present_mode;
+        set_display_mode(&cfg, &mut window, monitor.get());
+    }
+    #[cfg(target_arch = "wasm32")]
+    if cfg.display_mode == DisplayMode::Fullscreen {
+        set_fullscreen(true, &mut window);
+    }
+}
+
+/// Applies display changes from the menu
+pub fn update_window(
+    mut config_event: EventReader<ConfigChanged>,
+    mut ui_scale: ResMut<UiScale>,
+    mut window: Query<&mut Window, With<PrimaryWindow>>,
+    #[cfg(not(target_arch = "wasm32"))] monitor: CurrentMonitor,
+) {
+    let Some(ConfigChanged { previous, current }) = config_event.iter().last() else { return };
+    if previous.ui_scale != current.ui_scale {
+        ui_scale.scale = current.ui_scale as f64;
+    }
+    let Ok(mut window) = window.get_single_mut() else { return };
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        if previous.display_mode != current.display_mode {
+            set_display_mode(current, &mut window, monitor.get());
+        } else if previous.resolution != current.resolution
+            && current.display_mode == DisplayMode::Windowed
+        {
+            set_resolution(current.resolution, &mut window);
+        }
+        if previous.present_mode != current.present_mode {
+            window.present_mode = current.present_mode;
+        }
+    }
+    #[cfg(target_arch = "wasm32")]
+    if previous.display_mode != current.display_mode {
+        set_fullscreen(current.display_mode == DisplayMode::Fullscreen, &mut window);
+    }
+}
+
+/// Position and size in physical pixels of the monitor showing the `PrimaryWindow`
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(SystemParam)]
+pub struct CurrentMonitor<'w, 's> {
+    winit_windows: NonSend<'w, WinitWindows>,
+    primary_window: Query<'w, 's, Entity, With<PrimaryWindow>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl CurrentMonitor<'_, '_> {
+    fn get(&self) -> Option<(IVec2, UVec2)> {
+        let entity = self.primary_window.get_single().ok()?;
+        let monitor = self.winit_windows.get_window(entity)?.current_monitor()?;
+        let (position, size) = (monitor.position(), monitor.size());
+        Some((
+            IVec2::new(position.x, position.y),
+            UVec2::new(size.width, size.height),
+        ))
+    }
+}
+
+/// Restores the stored window position, centering the window if it would be off-screen, e.g.
+/// because the monitor it was on got unplugged
+#[cfg(not(target_arch = "wasm32"))]
+pub fn restore_window_position(
+    cfg: Res<GameCfg>,
+    winit_windows: NonSend<WinitWindows>,
+    mut window: Query<(Entity, &mut Window), With<PrimaryWindow>>,
+) {
+    let Some((x, y)) = cfg.window_position else { return };
+    let Ok((entity, mut window)) = window.get_single_mut() else { return };
+    let on_screen = winit_windows.get_window(entity).map_or(false, |winit_window| {
+        winit_window.available_monitors().any(|monitor| {
+            let (origin, size) = (monitor.position(), monitor.size());
+            (origin.x..origin.x + size.width as i32).contains(&x)
+                && (origin.y..origin.y + size.height as i32).contains(&y)
+        })
+    });
+    window.position = if on_screen {
+        WindowPosition::At(IVec2::new(x, y))
+    } else {
+        WindowPosition::Centered(MonitorSelection::Current)
+    };
+}
+
+/// Stores the size and position of the window whenever it is resized or moved, unless it is
+/// fullscreen or fit to the screen
+#[cfg(not(target_arch = "wasm32"))]
+pub fn track_window(
+    mut resized_event: EventReader<WindowResized>,
+    mut moved_event: EventReader<WindowMoved>,
+    mut config_event: EventWriter<ConfigChanged>,
+    mut cfg: ResMut<GameCfg>,
+    window: Query<&Window, With<PrimaryWindow>>,
+) {
+    let changed = resized_event.iter().count() + moved_event.iter().count() > 0;
+    let Ok(window) = window.get_single() else { return };
+    if !changed || cfg.display_mode != DisplayMode::Windowed {
+        return;
+    }
+    let previous = *cfg;
+    let resolution = (window.width() as u16, window.height() as u16);
+    let window_position = match window.position {
+        WindowPosition::At(position) => Some((position.x, position.y)),
+        _ => previous.window_position,
+    };
+    if (resolution, window_position) == (previous.resolution, previous.window_position) {
+        return;
+    }
+    cfg.resolution = resolution;
+    cfg.window_position = window_position;
+    config_event.send(ConfigChanged {
+        previous,
+        current: *cfg,
+    });
+}
+
+/// Asks before closing the window during a game, by opening the `ConfirmQuit` screen instead.
+/// Needs `WindowPlugin::close_when_requested` to be off, otherwise closes right away. While loading
+/// there is neither a game nor the `MenuTheme`
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) fn confirm_window_close(
+    mut commands: Commands,
+    mut close_event: EventReader<WindowCloseRequested>,
+    mut action_event: EventWriter<Actions>,
+    mut app_event: EventWriter<AppExit>,
+    mut cfg: ResMut<GameCfg>,
+    state: Res<State<GameState>>,
+    theme: Option<Res<MenuTheme>>,
+    assets: MenuAssets,
+) {
+    if close_event.iter().count() == 0 {
+        return;
+    }
+    if !cfg.new_game || cfg.outcome.is_some() {
+        app_event.send(AppExit);
+        return;
+    }
+    cfg.quit_requested = true;
+    match state.0 {
+        // Paused like with the Pause key, the menu then opens at `ConfirmQuit`
+        GameState::Game => action_event.send(Actions::Pause),
+        _ => {
+            let Some(theme) = theme else { return };
+            commands.insert_resource(root_menu(&state.0, &cfg, &theme, &assets))
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn set_resolution((width, height): (u16, u16), window: &mut Window) {
+    window.resolution.set(width as f32, height as f32);
+}
+
+/// Fitting to the screen keeps the window in `WindowMode::Windowed`, only without decorations and
+/// sized to the `monitor`. winit doesn't tell the work area, so it covers taskbars as well
+#[cfg(not(target_arch = "wasm32"))]
+fn set_display_mode(cfg: &GameCfg, window: &mut Window, monitor: Option<(IVec2, UVec2)>) {
+    window.decorations = cfg.display_mode != DisplayMode::FitToScreen;
+    match cfg.display_mode {
+        DisplayMode::Windowed => {
+            window.mode = WindowMode::Windowed;
+            set_resolution(cfg.resolution, window);
+        }
+        DisplayMode::Fullscreen => window.mode = WindowMode::BorderlessFullscreen,
+        DisplayMode::FitToScreen => {
+            window.mode = WindowMode::Windowed;
+            let Some((position, size)) = monitor else {
+                warn!("No monitor to fit the window to");
+                return;
+            };
+            window.position = WindowPosition::At(position);
+            window.resolution.set_physical_resolution(size.x, size.y);
+        }
+    }
+}
+
+/// Browsers only allow fullscreen for the canvas, and only following a user gesture
+#[cfg(target_arch = "wasm32")]
+fn set_fullscreen(fullscreen: bool, _window: &mut Window) {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else { return };
+    if !fullscreen {
+        document.exit_fullscreen();
+        return;
+    }
+    let requested = match document.query_selector("#bevy") {
+        Ok(Some(canvas)) => canvas.request_fullscreen().is_ok(),
+        _ => false,
+    };
+    if !requested {
+        warn!("The browser refused to enter fullscreen");
+    }
+}
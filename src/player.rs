@@ -1,5 +1,7 @@
-use crate::actions::Actions;
+use crate::actions::PlayerActions;
+use crate::cleanup::GameEntity;
 use crate::loading::TextureAssets;
+use crate::menu::GameCfg;
 use crate::GameState;
 use bevy::prelude::*;
 
@@ -9,7 +11,8 @@ pub struct PlayerPlugin;
 pub struct Player;
 
 /// This plugin handles player related stuff like movement
-/// Player logic is only active during the State `GameState::Playing`
+/// Player logic is only active during the State `GameState::Game`. The player is a `GameEntity`, so
+/// it stays in the world during a pause and is despawned with the rest of the game
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.add_system(spawn_player.in_schedule(OnEnter(GameState::Game)))
@@ -17,30 +20,34 @@ impl Plugin for PlayerPlugin {
     }
 }
 
-fn spawn_player(mut commands: Commands, textures: Res<TextureAssets>) {
+/// Entering `GameState::Game` also resumes a paused game, which keeps its player
+fn spawn_player(
+    mut commands: Commands,
+    textures: Res<TextureAssets>,
+    player_query: Query<(), With<Player>>,
+) {
+    if !player_query.is_empty() {
+        return;
+    }
     commands
         .spawn(SpriteBundle {
             texture: textures.texture_bevy.clone(),
             transform: Transform::from_translation(Vec3::new(0., 0., 1.)),
             ..Default::default()
         })
-        .insert(Player);
+        .insert((Player, GameEntity));
 }
 
+/// Only runs in `GameState::Game` and is scaled by `Res<Time>`, which the pause menu stops
 fn move_player(
     time: Res<Time>,
-    actions: Res<Actions>,
+    actions: Res<PlayerActions>,
+    cfg: Res<GameCfg>,
     mut player_query: Query<&mut Transform, With<Player>>,
 ) {
-    if actions.player_movement.is_none() {
-        return;
-    }
-    let speed = 150.;
-    let movement = Vec3::new(
-        actions.player_movement.unwrap().x * speed * time.delta_seconds(),
-        actions.player_movement.unwrap().y * speed * time.delta_seconds(),
-        0.,
-    );
+    let Some(player_movement) = actions.player_movement else { return };
+    let speed = 150. * cfg.speed;
+    let movement = (player_movement * speed * time.delta_seconds()).extend(0.);
     for mut player_transform in &mut player_query {
         player_transform.translation += movement;
     }
@@ -0,0 +1,47 @@
+/// Seeded randomness for a game, so that the same seed plays out the same way, e.g. for tests or
+/// daily challenges
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+
+/// Created for every new game from `GameCfg::seed`. Game systems should draw their randomness from
+/// it, instead of `rand::thread_rng`
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl GameRng {
+    /// A `seed` of 0 picks a random one
+    pub fn new(seed: u64) -> Self {
+        let seed = if seed == 0 {
+            rand::thread_rng().gen_range(1..=u64::MAX)
+        } else {
+            seed
+        };
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+    /// The seed of this game, to show or share it
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
@@ -0,0 +1,205 @@
+/// Save games in numbered slots. The template has no progress of its own; games keep theirs in a
+/// resource implementing `SaveData` and add a `SaveDataPlugin` for it. Each slot is stored as RON
+/// next to the config, or in `localStorage` on wasm
+use crate::storage;
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// Number of slots offered by the `SaveGame` screen
+pub const SAVE_SLOTS: usize = 3;
+
+/// Slot reserved for the autosave, following the regular slots
+pub const AUTOSAVE_SLOT: SaveSlot = SaveSlot(SAVE_SLOTS as u8);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct SaveSlot(pub u8);
+
+impl SaveSlot {
+    fn file_name(&self) -> String {
+        format!("save_{}.ron", self.0)
+    }
+}
+
+/// Writes the `SaveData` resource to the slot
+pub struct SaveGameEvent(pub SaveSlot);
+
+/// Replaces the `SaveData` resource with the one stored in the slot
+pub struct LoadGameEvent(pub SaveSlot);
+
+/// Sent once a slot is written, `timestamp` is in seconds since the unix epoch
+pub struct GameSaved {
+    pub slot: SaveSlot,
+    pub timestamp: u64,
+}
+
+/// The progress of a game, kept in a resource
+pub trait SaveData: Resource + Serialize + DeserializeOwned {}
+
+#[derive(Serialize, Deserialize)]
+struct SaveFile<T> {
+    timestamp: u64,
+    data: T,
+}
+
+/// The part of a `SaveFile` read to list the slots, without knowing the `SaveData`
+#[derive(Deserialize)]
+struct SaveHeader {
+    timestamp: u64,
+}
+
+/// Writes `data` to the slot, returning the timestamp of the save
+pub fn save<T: SaveData>(slot: SaveSlot, data: &T) -> Option<u64> {
+    let file = SaveFile {
+        timestamp: storage::now(),
+        data,
+    };
+    match ron::to_string(&file) {
+        Ok(contents) => storage::write(&slot.file_name(), &contents).then_some(file.timestamp),
+        Err(err) => {
+            warn!("Failed to serialize {}: {err}", slot.file_name());
+            None
+        }
+    }
+}
+
+/// Reads the data stored in the slot, `None` if it is empty or malformed
+pub fn load<T: SaveData>(slot: SaveSlot) -> Option<T> {
+    let contents = storage::read(&slot.file_name())?;
+    match ron::from_str::<SaveFile<T>>(&contents) {
+        Ok(file) => Some(file.data),
+        Err(err) => {
+            warn!("Ignoring malformed {}: {err}", slot.file_name());
+            None
+        }
+    }
+}
+
+/// Timestamps of all slots including the autosave, `None` for the empty ones
+pub fn timestamps() -> [Option<u64>; SAVE_SLOTS + 1] {
+    std::array::from_fn(|i| {
+        let contents = storage::read(&SaveSlot(i as u8).file_name())?;
+        ron::from_str::<SaveHeader>(&contents)
+            .ok()
+            .map(|header| header.timestamp)
+    })
+}
+
+/// Formats a timestamp as UTC date and time, like `2023-04-01 13:37`
+pub fn format_timestamp(timestamp: u64) -> String {
+    let (days, seconds) = (timestamp / 86400, timestamp % 86400);
+    // Civil from days, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z / 146097;
+    let doe = z % 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    format!(
+        "{year}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+/// The systems of the `SaveDataPlugin`s. Systems sending `SaveGameEvent` run before it, so that
+/// the game is saved in the same frame, before any state transition
+#[derive(SystemSet, Debug, PartialEq, Eq, Clone, Hash)]
+pub struct SaveSet;
+
+/// Registers the save events, the saving itself is done by a `SaveDataPlugin`
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SaveGameEvent>()
+            .add_event::<LoadGameEvent>()
+            .add_event::<GameSaved>();
+    }
+}
+
+/// Saves and loads the `SaveData` resource `T` on `SaveGameEvent` and `LoadGameEvent`
+pub struct SaveDataPlugin<T>(PhantomData<T>);
+
+impl<T> Default for SaveDataPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: SaveData> Plugin for SaveDataPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.add_system(save_game::<T>.in_set(SaveSet))
+            .add_system(load_game::<T>.in_set(SaveSet));
+    }
+}
+
+fn save_game<T: SaveData>(
+    mut save_event: EventReader<SaveGameEvent>,
+    mut saved_event: EventWriter<GameSaved>,
+    data: Option<Res<T>>,
+) {
+    for SaveGameEvent(slot) in save_event.iter() {
+        let Some(data) = &data else {
+            warn!("Nothing to save in slot {}", slot.0);
+            continue;
+        };
+        if let Some(timestamp) = save(*slot, &**data) {
+            saved_event.send(GameSaved {
+                slot: *slot,
+                timestamp,
+            });
+        }
+    }
+}
+
+fn load_game<T: SaveData>(mut commands: Commands, mut load_event: EventReader<LoadGameEvent>) {
+    let Some(LoadGameEvent(slot)) = load_event.iter().last() else { return };
+    if let Some(data) = load::<T>(*slot) {
+        commands.insert_resource(data);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Serialize, Deserialize, Debug, PartialEq)]
+    struct Progress {
+        level: u32,
+        name: String,
+    }
+
+    impl SaveData for Progress {}
+
+    #[test]
+    fn formats_timestamps_in_utc() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(951868740), "2000-02-29 23:59");
+        assert_eq!(format_timestamp(1680356220), "2023-04-01 13:37");
+    }
+
+    #[test]
+    fn loads_what_was_saved() {
+        let slot = SaveSlot(1);
+        let progress = Progress {
+            level: 7,
+            name: "Test".to_string(),
+        };
+        assert!(save(slot, &progress).is_some());
+        assert_eq!(load::<Progress>(slot), Some(progress));
+    }
+
+    #[test]
+    fn ignores_malformed_slots() {
+        let slot = SaveSlot(1);
+        assert!(storage::write(
+            &slot.file_name(),
+            "(timestamp: 1, data: 42)"
+        ));
+        assert_eq!(load::<Progress>(slot), None);
+    }
+}
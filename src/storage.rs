@@ -0,0 +1,85 @@
+/// Storage of small text files, like the config and the save games. On desktop they are files in
+/// the platform config dir, on wasm they are entries of `localStorage`
+use bevy::prelude::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+fn path(name: &str) -> Option<std::path::PathBuf> {
+    // Tests keep away from the config and the saves of the installed game, and from each other as
+    // they run in parallel, each on a thread named after it
+    if cfg!(test) {
+        return Some(test_dir().join(name));
+    }
+    directories::ProjectDirs::from("", "", env!("CARGO_PKG_NAME"))
+        .map(|dirs| dirs.config_dir().join(name))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read(name: &str) -> Option<String> {
+    std::fs::read_to_string(path(name)?).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn test_dir() -> std::path::PathBuf {
+    let thread = std::thread::current();
+    let test = thread.name().unwrap_or("main").replace("::", "-");
+    std::env::temp_dir()
+        .join(concat!(env!("CARGO_PKG_NAME"), "-tests"))
+        .join(test)
+}
+
+/// Lets a test start without the files a previous run of it left behind
+#[cfg(all(test, not(target_arch = "wasm32")))]
+pub fn clear() {
+    let _ = std::fs::remove_dir_all(test_dir());
+}
+
+/// Returns whether the file was written, failures are logged
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write(name: &str, contents: &str) -> bool {
+    let Some(path) = path(name) else {
+        warn!("No config dir available, {name} is not saved");
+        return false;
+    };
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(&path, contents));
+    if let Err(err) = &result {
+        warn!("Failed to write {}: {err}", path.display());
+    }
+    result.is_ok()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn read(name: &str) -> Option<String> {
+    local_storage()?.get_item(name).ok()?
+}
+
+/// Returns whether the entry was written, failures are logged
+#[cfg(target_arch = "wasm32")]
+pub fn write(name: &str, contents: &str) -> bool {
+    let saved = local_storage().map_or(false, |storage| storage.set_item(name, contents).is_ok());
+    if !saved {
+        warn!("Failed to write {name} to localStorage");
+    }
+    saved
+}
+
+/// Seconds since the unix epoch
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// Seconds since the unix epoch
+#[cfg(target_arch = "wasm32")]
+pub fn now() -> u64 {
+    (js_sys::Date::now() / 1000.) as u64
+}
@@ -0,0 +1,145 @@
+use crate::menu::GameCfg;
+use crate::GameState;
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+pub struct TransitionPlugin;
+
+/// This plugin fades the screen to black and back when switching between `GameState`s
+/// Send a `FadeTransition` instead of setting `NextState` directly, the state is changed once the
+/// screen is fully black
+impl Plugin for TransitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<FadeTransition>()
+            .init_resource::<FadeDuration>()
+            .add_system(start_fade)
+            .add_system(update_fade.after(start_fade));
+    }
+}
+
+/// Requests a fade into the given state. Requests the current state doesn't allow, see
+/// `check_transition`, are logged and ignored
+pub struct FadeTransition(pub GameState);
+
+/// Why the game can't switch from one state to the other, if it can't
+fn check_transition(from: &GameState, to: &GameState, cfg: &GameCfg) -> Result<(), &'static str> {
+    match (from, to) {
+        (_, GameState::Loading) => Err("the assets are only loaded once"),
+        (GameState::Loading, _) => Err("the assets are still loading"),
+        (GameState::Game, GameState::Game) | (GameState::Menu, GameState::Menu) => {
+            Err("it is already the current state")
+        }
+        // The `GameOver` screen is shown in the menu too, the outcome is then set
+        (GameState::Game, GameState::Menu) => Ok(()),
+        (GameState::Menu, GameState::Game) if !cfg.new_game => Err("no game was started"),
+        (GameState::Menu, GameState::Game) => Ok(()),
+    }
+}
+
+/// Duration in seconds of each half of the fade
+#[derive(Resource)]
+pub struct FadeDuration(pub f32);
+
+impl Default for FadeDuration {
+    fn default() -> Self {
+        FadeDuration(0.25)
+    }
+}
+
+#[derive(Component)]
+struct Fade {
+    timer: Timer,
+    /// State to switch to at full opacity, `None` while fading out
+    next: Option<GameState>,
+}
+
+fn start_fade(
+    mut commands: Commands,
+    mut fade_event: EventReader<FadeTransition>,
+    duration: Res<FadeDuration>,
+    state: Res<State<GameState>>,
+    cfg: Res<GameCfg>,
+    fade: Query<(), With<Fade>>,
+) {
+    let Some(FadeTransition(next)) = fade_event.iter().last() else { return };
+    // A running fade is finished first
+    if !fade.is_empty() {
+        return;
+    }
+    let current = &state.0;
+    if let Err(reason) = check_transition(current, next, &cfg) {
+        warn!("Ignoring the transition from {current:?} to {next:?}, {reason}");
+        return;
+    }
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                ..default()
+            },
+            background_color: Color::rgba(0., 0., 0., 0.).into(),
+            // Only below overlays like the FPS counter
+            z_index: ZIndex::Global(i32::MAX - 1),
+            focus_policy: FocusPolicy::Block,
+            ..default()
+        },
+        Fade {
+            timer: Timer::from_seconds(duration.0, TimerMode::Once),
+            next: Some(next.clone()),
+        },
+    ));
+}
+
+fn update_fade(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut fade: Query<(Entity, &mut Fade, &mut BackgroundColor)>,
+) {
+    for (entity, mut fade, mut color) in &mut fade {
+        // The game clock is paused while the pause menu is open
+        fade.timer.tick(time.raw_delta());
+        let progress = fade.timer.percent();
+        color.0.set_a(if fade.next.is_some() {
+            progress
+        } else {
+            1. - progress
+        });
+        if !fade.timer.finished() {
+            continue;
+        }
+        match fade.next.take() {
+            Some(next) => {
+                next_state.set(next);
+                fade.timer.reset();
+            }
+            None => commands.entity(entity).despawn_recursive(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_transitions_without_a_game() {
+        let cfg = GameCfg::default();
+        assert!(check_transition(&GameState::Menu, &GameState::Game, &cfg).is_err());
+        let mut started = cfg;
+        started.new_game = true;
+        assert!(check_transition(&GameState::Menu, &GameState::Game, &started).is_ok());
+        assert!(check_transition(&GameState::Game, &GameState::Menu, &started).is_ok());
+    }
+
+    #[test]
+    fn rejects_loading_and_the_current_state() {
+        let mut cfg = GameCfg::default();
+        cfg.new_game = true;
+        assert!(check_transition(&GameState::Menu, &GameState::Loading, &cfg).is_err());
+        assert!(check_transition(&GameState::Loading, &GameState::Menu, &cfg).is_err());
+        assert!(check_transition(&GameState::Game, &GameState::Game, &cfg).is_err());
+        assert!(check_transition(&GameState::Menu, &GameState::Menu, &cfg).is_err());
+    }
+}